@@ -0,0 +1,65 @@
+/*
+ * The C interface of the rolling_stats crate, built with the `ffi` feature:
+ * cargo rustc --release --features ffi --crate-type cdylib
+ *
+ * The functions never unwind into the calling code, a panic inside of the library aborts the process.
+ */
+
+#ifndef ROLLING_STATS_H
+#define ROLLING_STATS_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The types of the values. */
+#define ROLLING_STATS_I32 0
+#define ROLLING_STATS_F32 1
+#define ROLLING_STATS_F64 2
+
+/* The byte orders of the values. */
+#define ROLLING_STATS_LITTLE_ENDIAN 0
+#define ROLLING_STATS_BIG_ENDIAN 1
+#define ROLLING_STATS_NATIVE_ENDIAN 2
+
+/* An opaque window computing the statistics in double. */
+typedef struct RollingStatsHandle RollingStatsHandle;
+
+/* The statistics of the window, min and max are zero for an empty window. */
+typedef struct RollingStatsSummary {
+    size_t len;
+    double mean;
+    double std_dev;
+    double min;
+    double max;
+} RollingStatsSummary;
+
+/* Creates a window of `window` values, returns NULL for an unknown type or byte order. */
+RollingStatsHandle *rolling_stats_new(uint32_t value_type, uint32_t endian, size_t window);
+
+/* Frees the window, NULL is ignored. */
+void rolling_stats_free(RollingStatsHandle *stats);
+
+/* Parses the raw data into the window, returns the number of consumed bytes or -1 on error. */
+intptr_t rolling_stats_write(RollingStatsHandle *stats, const uint8_t *data, size_t len);
+
+/* Returns the mean of the window, NaN for NULL. */
+double rolling_stats_mean(const RollingStatsHandle *stats);
+
+/* Returns the standard deviation of the window, NaN for NULL. */
+double rolling_stats_std_dev(const RollingStatsHandle *stats);
+
+/* Writes the statistics of the window into the summary, returns 0 on success or -1 for NULL pointers. */
+int32_t rolling_stats_summary(const RollingStatsHandle *stats, RollingStatsSummary *summary);
+
+/* Empties the window, discarding the incomplete data. */
+void rolling_stats_clear(RollingStatsHandle *stats);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ROLLING_STATS_H */
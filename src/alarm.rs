@@ -0,0 +1,198 @@
+//! Alarm driven by the statistics of the window, using hysteresis to avoid toggling when the statistic oscillates around a threshold.
+
+use crate::{convertfloat::LossyFloatConvertible, RollingStats, Statistics, WindowStorage};
+use core::ops::Add;
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+
+/// The state of an alarm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlarmState {
+    /// The alarm is not asserted.
+    #[default]
+    Cleared,
+    /// The alarm is asserted.
+    Asserted,
+}
+
+impl AlarmState {
+    /// Returns the state following this one for the value of the statistic, asserting above the `high` threshold and clearing below the `low` one.
+    fn next<O: Float>(self, value: O, high: O, low: O) -> Self {
+        match self {
+            AlarmState::Cleared if value > high => AlarmState::Asserted,
+            AlarmState::Asserted if value < low => AlarmState::Cleared,
+            state => state,
+        }
+    }
+}
+
+/// The statistic of the window driving the `ThresholdAlarm`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AlarmSource {
+    /// The mean of the window.
+    #[default]
+    Mean,
+    /// The newest item of the window.
+    Latest,
+}
+
+/// An alarm with hysteresis, asserted once the statistic rises above the `high` threshold and cleared only once it falls below the `low` threshold,
+/// so that it doesn't toggle while the statistic oscillates around a single threshold.
+/// Unlike the `alarm_state` method of the window, the alarm counts its transitions and can be driven by the newest item of the window.
+/// # Examples
+/// ```
+/// use rolling_stats::{AlarmState, LittleEndian, RollingStats, ThresholdAlarm};
+///
+/// let mut roller = RollingStats::<i32, LittleEndian, 2>::new();
+/// let mut alarm = ThresholdAlarm::new(10.0, 5.0);
+/// for value in [8i32, 14, 4, 4] {
+///     roller.feed(&value.to_le_bytes()).unwrap();
+///     alarm.update(&roller);
+/// }
+///
+/// assert_eq!(alarm.state(), AlarmState::Cleared);
+/// assert_eq!((alarm.asserted_count(), alarm.cleared_count()), (1, 1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ThresholdAlarm<O = f32> {
+    high: O,
+    low: O,
+    source: AlarmSource,
+    state: AlarmState,
+    asserted: usize,
+    cleared: usize,
+}
+
+impl<O: Float> ThresholdAlarm<O> {
+    /// Creates a cleared alarm driven by the mean of the window, the `low` threshold is expected not to exceed the `high` one.
+    pub fn new(high: O, low: O) -> Self {
+        Self {
+            high,
+            low,
+            source: AlarmSource::default(),
+            state: AlarmState::default(),
+            asserted: 0,
+            cleared: 0,
+        }
+    }
+
+    /// Sets the statistic of the window driving the alarm.
+    pub fn with_source(mut self, source: AlarmSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Updates the state of the alarm with the statistic of the window and returns the state.
+    /// The state is kept for an empty window.
+    pub fn update<T, E, const WINDOW_SIZE: usize, S>(
+        &mut self,
+        stats: &RollingStats<T, E, WINDOW_SIZE, O, S>,
+    ) -> AlarmState
+    where
+        T: Copy + Default + Add<T, Output = T> + LossyFloatConvertible<O>,
+        StandardNormal: Distribution<O>,
+        S: WindowStorage<T>,
+    {
+        let value = match (self.source, stats.latest()) {
+            (_, None) => return self.state,
+            (AlarmSource::Mean, Some(_)) => stats.mean(),
+            (AlarmSource::Latest, Some(latest)) => latest.convert(),
+        };
+        self.update_value(value)
+    }
+
+    /// Updates the state of the alarm with the value of the statistic computed elsewhere and returns the state.
+    pub fn update_value(&mut self, value: O) -> AlarmState {
+        let state = self.state.next(value, self.high, self.low);
+        match (self.state, state) {
+            (AlarmState::Cleared, AlarmState::Asserted) => self.asserted += 1,
+            (AlarmState::Asserted, AlarmState::Cleared) => self.cleared += 1,
+            _ => {}
+        }
+        self.state = state;
+
+        state
+    }
+
+    /// Returns the current state of the alarm.
+    pub fn state(&self) -> AlarmState {
+        self.state
+    }
+
+    /// Returns the number of times the alarm was asserted.
+    pub fn asserted_count(&self) -> usize {
+        self.asserted
+    }
+
+    /// Returns the number of times the alarm was cleared.
+    pub fn cleared_count(&self) -> usize {
+        self.cleared
+    }
+
+    /// Clears the alarm and resets the transition counts.
+    pub fn reset(&mut self) {
+        self.state = AlarmState::default();
+        self.asserted = 0;
+        self.cleared = 0;
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    /// Updates and returns the state of the alarm driven by the mean of the window.
+    /// The alarm is asserted once the mean rises above the `high` threshold and cleared only once the mean falls below the `low` threshold,
+    /// the state is kept between the thresholds.
+    pub fn alarm_state(&mut self, high: O, low: O) -> AlarmState {
+        self.alarm = self.alarm.next(self.mean(), high, low);
+
+        self.alarm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LittleEndian;
+
+    #[test]
+    fn hysteresis() {
+        let mut roller = RollingStats::<i32, LittleEndian, 1>::default();
+        let mut state = |value: i32| {
+            let _ = roller.feed(&value.to_le_bytes()).unwrap();
+            roller.alarm_state(10.0, 5.0)
+        };
+
+        assert_eq!(state(7), AlarmState::Cleared);
+        assert_eq!(state(11), AlarmState::Asserted);
+        assert_eq!(state(7), AlarmState::Asserted);
+        assert_eq!(state(4), AlarmState::Cleared);
+        assert_eq!(state(7), AlarmState::Cleared);
+    }
+
+    #[test]
+    fn threshold_alarm() {
+        let mut roller = RollingStats::<i32, LittleEndian, 3>::default();
+        let mut mean = ThresholdAlarm::new(10.0, 5.0);
+        let mut latest = ThresholdAlarm::new(10.0, 5.0).with_source(AlarmSource::Latest);
+        assert_eq!(latest.update(&roller), AlarmState::Cleared);
+
+        for value in [12i32, 3, 12, 12, 1, 1] {
+            roller.feed(&value.to_le_bytes()).unwrap();
+            mean.update(&roller);
+            latest.update(&roller);
+        }
+
+        assert_eq!(mean.state(), AlarmState::Cleared);
+        assert_eq!((mean.asserted_count(), mean.cleared_count()), (1, 1));
+        assert_eq!((latest.asserted_count(), latest.cleared_count()), (2, 2));
+
+        assert_eq!(mean.update_value(11.0), AlarmState::Asserted);
+        mean.reset();
+        assert_eq!(mean.state(), AlarmState::Cleared);
+        assert_eq!(mean.asserted_count(), 0);
+    }
+}
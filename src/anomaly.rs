@@ -0,0 +1,189 @@
+//! Detection of the anomalous values, lying further than `k` standard deviations from the mean of the window they enter.
+
+use crate::{convertfloat::LossyFloatConvertible, RollingStats, Statistics, WindowStorage};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::ops::Add;
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+
+/// A value lying outside of the `mean ± k·std_dev` interval of the window before the value entered it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Anomaly<T, O = f32> {
+    /// The anomalous value.
+    pub value: T,
+    /// The mean of the window before the value entered it.
+    pub mean: O,
+    /// The standard deviation of the window before the value entered it.
+    pub std_dev: O,
+}
+
+/// Counts and collects the anomalies of the values entering the window.
+pub(crate) struct AnomalyDetector<S, T, O> {
+    k: O,
+    count: usize,
+    /// The maximal number of the queued anomalies.
+    capacity: usize,
+    /// The number of the anomalies dropped from the full queue.
+    dropped: usize,
+    anomalies: VecDeque<Anomaly<T, O>>,
+    /// Checks the value against the window, captured when enabling the detection, so that pushing values doesn't require the statistics bounds.
+    check: fn(&S, T, &O) -> Option<Anomaly<T, O>>,
+}
+
+impl<S, T: Clone, O: Clone> Clone for AnomalyDetector<S, T, O> {
+    fn clone(&self) -> Self {
+        Self {
+            k: self.k.clone(),
+            count: self.count,
+            capacity: self.capacity,
+            dropped: self.dropped,
+            anomalies: self.anomalies.clone(),
+            check: self.check,
+        }
+    }
+}
+
+impl<S, T, O> AnomalyDetector<S, T, O> {
+    pub(crate) fn clear(&mut self) {
+        self.count = 0;
+        self.dropped = 0;
+        self.anomalies.clear();
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    /// Enables the detection of the anomalous values - the values lying outside of the `mean ± k·std_dev` interval of the window,
+    /// computed before the value enters the window. The values entering a window of less than two items are never anomalous.
+    /// With the time decay enabled, both the mean and the standard deviation of the interval are the decayed ones.
+    /// The anomalous values still enter the window, they are counted and queued until taken using the `take_anomalies` method,
+    /// the number of anomalies of a single write is reported by the `ingest` method.
+    /// At most `capacity` anomalies are queued, the oldest ones are dropped once the queue is full and counted by the `dropped_anomalies` method.
+    pub fn with_anomaly_detection(mut self, k: O, capacity: usize) -> Self {
+        self.anomaly = Some(AnomalyDetector {
+            k,
+            count: 0,
+            capacity,
+            dropped: 0,
+            anomalies: VecDeque::with_capacity(capacity.min(64)),
+            check: Self::check_anomaly,
+        });
+        self
+    }
+
+    /// Returns the anomaly if the value lies outside of the `mean ± k·std_dev` interval of the window.
+    fn check_anomaly(&self, value: T, k: &O) -> Option<Anomaly<T, O>> {
+        if self.len() < 2 {
+            return None;
+        }
+
+        let (mean, std_dev) = (self.mean(), self.std_dev());
+        if (value.convert() - mean).abs() > *k * std_dev {
+            Some(Anomaly {
+                value,
+                mean,
+                std_dev,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Returns the number of the anomalous values detected since the window was created or cleared.
+    /// Zero unless the detection is enabled using the `with_anomaly_detection` method.
+    pub fn anomaly_count(&self) -> usize {
+        self.anomaly.as_ref().map_or(0, |detector| detector.count)
+    }
+
+    /// Returns the anomalies detected since the last call, the oldest first.
+    pub fn take_anomalies(&mut self) -> Vec<Anomaly<T, O>> {
+        self.anomaly
+            .as_mut()
+            .map_or_else(Vec::new, |detector| detector.anomalies.drain(..).collect())
+    }
+
+    /// Returns the number of the anomalies dropped from the full queue since the window was created or cleared.
+    pub fn dropped_anomalies(&self) -> usize {
+        self.anomaly.as_ref().map_or(0, |detector| detector.dropped)
+    }
+
+    /// Checks the value about to enter the window if the detection is enabled.
+    pub(crate) fn detect_anomaly(&mut self, value: T) {
+        let anomaly = match self.anomaly.as_ref() {
+            Some(detector) => (detector.check)(self, value, &detector.k),
+            None => return,
+        };
+
+        if let (Some(anomaly), Some(detector)) = (anomaly, self.anomaly.as_mut()) {
+            detector.count += 1;
+            if detector.anomalies.len() >= detector.capacity {
+                detector.dropped += 1;
+                if detector.anomalies.pop_front().is_none() {
+                    return;
+                }
+            }
+            detector.anomalies.push_back(anomaly);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Anomaly, BigEndian, RollingStats};
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn detects_anomalies() {
+        let mut roller = RollingStats::<i32, BigEndian, 4>::new().with_anomaly_detection(2.0, 8);
+        let values: Vec<u8> = [10i32, 12, 40, 11, 10, 12, 9]
+            .iter()
+            .flat_map(|value| value.to_be_bytes())
+            .collect();
+
+        let report = roller.ingest(&values).unwrap();
+        assert_eq!(report.anomalies, 1);
+        assert_eq!(roller.anomaly_count(), 1);
+        assert_eq!(
+            roller.take_anomalies(),
+            vec![Anomaly {
+                value: 40,
+                mean: 11.0,
+                std_dev: 2f32.sqrt(),
+            }]
+        );
+        assert!(roller.take_anomalies().is_empty());
+
+        roller.clear();
+        assert_eq!(roller.anomaly_count(), 0);
+    }
+
+    #[test]
+    fn drops_oldest_anomalies() {
+        let mut roller = RollingStats::<i32, BigEndian, 8>::new().with_anomaly_detection(2.0, 2);
+        roller.extend([10, 12, 10, 12, 100, 200, 400, 800].iter().copied());
+
+        assert_eq!(roller.anomaly_count(), 4);
+        assert_eq!(roller.dropped_anomalies(), 2);
+        let values: Vec<i32> = roller
+            .take_anomalies()
+            .iter()
+            .map(|anomaly| anomaly.value)
+            .collect();
+        assert_eq!(values, vec![400, 800]);
+
+        let mut disabled = RollingStats::<i32, BigEndian, 8>::new().with_anomaly_detection(2.0, 0);
+        disabled.extend([10, 12, 10, 12, 100].iter().copied());
+        assert_eq!(disabled.anomaly_count(), 1);
+        assert_eq!(disabled.dropped_anomalies(), 1);
+        assert!(disabled.take_anomalies().is_empty());
+    }
+}
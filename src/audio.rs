@@ -0,0 +1,285 @@
+//! Ingestion of the PCM samples of a single channel of a WAV file, such as for computing the rolling RMS or peak of an audio recording.
+
+use crate::{Error, LittleEndian, RollingStats, RuntimeWidth};
+use std::io::{Read, Write};
+
+/// The length of the longest format chunk, the one of the `WAVE_FORMAT_EXTENSIBLE` format, any following bytes are skipped.
+const FMT_CHUNK_SIZE: usize = 40;
+
+/// Denotes the encoding of the samples of a WAV file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WavFormat {
+    /// Signed little endian integers, or unsigned integers in the case of 8 bit samples.
+    Pcm,
+    /// Little endian IEEE floats.
+    Float,
+}
+
+/// The parameters of the samples of a WAV file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WavHeader {
+    /// The encoding of the samples.
+    pub format: WavFormat,
+    /// The number of the interleaved channels.
+    pub channels: u16,
+    /// The number of frames per second.
+    pub sample_rate: u32,
+    /// The number of significant bits of a sample, the samples are padded to whole bytes.
+    pub bits_per_sample: u16,
+}
+
+impl WavHeader {
+    /// Returns the size of a single sample in bytes.
+    pub fn sample_size(&self) -> usize {
+        (self.bits_per_sample as usize).div_ceil(8)
+    }
+
+    /// Returns the size of a frame - the samples of all of the channels - in bytes.
+    pub fn frame_size(&self) -> usize {
+        self.sample_size() * self.channels as usize
+    }
+
+    /// Creates a new `RollingStats` decoding the integer samples of the WAV file.
+    /// # Errors
+    /// Returns the `Error::InvalidFormat` error if the samples are floats or unsigned 8 bit integers,
+    /// these are decoded using the `RollingStats<f32, LittleEndian, _>` or `RollingStats<f64, LittleEndian, _>` instead.
+    pub fn pcm_window<const WINDOW_SIZE: usize, O>(
+        &self,
+    ) -> Result<RollingStats<i64, RuntimeWidth<LittleEndian>, WINDOW_SIZE, O>, Error> {
+        if self.format != WavFormat::Pcm || self.sample_size() < 2 {
+            return Err(Error::InvalidFormat(
+                "Only signed integer samples are decoded as integers.",
+            ));
+        }
+
+        RollingStats::try_with_width(self.sample_size())
+    }
+}
+
+/// Reads the header of a WAV file and then the samples of a selected channel.
+/// # Examples
+/// ```
+/// use rolling_stats::{Statistics, WavReader};
+///
+/// # let mut wav = b"RIFF\x28\0\0\0WAVEfmt \x10\0\0\0\x01\0\x02\0\x44\xac\0\0\x10\xb1\x02\0\x04\0\x10\0data\x04\0\0\0".to_vec();
+/// # wav.extend_from_slice(&[1, 0, 0xfd, 0xff]);
+/// let mut reader = WavReader::new(&wav[..]).unwrap();
+/// let mut roller = reader.header().pcm_window::<1024, f32>().unwrap();
+/// reader.copy_channel(1, &mut roller).unwrap();
+///
+/// assert_eq!(roller.mean(), -3.0);
+/// ```
+pub struct WavReader<R> {
+    reader: R,
+    header: WavHeader,
+    remaining: u64,
+}
+
+impl<R: Read> WavReader<R> {
+    /// Reads the header of the WAV file, skipping any chunks preceding the sample data.
+    /// # Errors
+    /// Returns the `Error::InvalidFormat` error if the file is not a WAV file or the samples are neither integers nor floats.
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        let mut riff = [0u8; 12];
+        reader.read_exact(&mut riff)?;
+        if &riff[..4] != b"RIFF" || &riff[8..] != b"WAVE" {
+            return Err(Error::InvalidFormat("Not a WAV file."));
+        }
+
+        let mut header = None;
+        loop {
+            let mut chunk = [0u8; 8];
+            reader.read_exact(&mut chunk)?;
+            let size = u32::from_le_bytes([chunk[4], chunk[5], chunk[6], chunk[7]]) as u64;
+
+            match (&chunk[..4], header) {
+                (b"fmt ", _) => {
+                    // the size comes from the file, so the chunk is never allocated according to it
+                    let mut fmt = [0u8; FMT_CHUNK_SIZE];
+                    let len = (size as usize).min(FMT_CHUNK_SIZE);
+                    reader.read_exact(&mut fmt[..len])?;
+                    header = Some(Self::parse_fmt(&fmt[..len])?);
+                    Self::skip(&mut reader, size - len as u64 + size % 2)?;
+                }
+                (b"data", Some(header)) => {
+                    return Ok(Self {
+                        reader,
+                        header,
+                        remaining: size,
+                    })
+                }
+                (b"data", None) => {
+                    return Err(Error::InvalidFormat("The data precede the format chunk."))
+                }
+                _ => Self::skip(&mut reader, size + size % 2)?,
+            }
+        }
+    }
+
+    /// Returns the parameters of the samples.
+    pub fn header(&self) -> &WavHeader {
+        &self.header
+    }
+
+    /// Reads the remaining samples, writing the raw samples of the channel to the writer, such as the `RollingStats`.
+    /// The samples are read in large chunks, a truncated last frame is ignored.
+    /// # Returns
+    /// Returns the number of the samples written.
+    /// # Errors
+    /// Returns the `Error::InvalidParameter` error if the channel doesn't exist, or the errors of the reader and the writer.
+    pub fn copy_channel<W: Write>(&mut self, channel: u16, writer: &mut W) -> Result<u64, Error> {
+        if channel >= self.header.channels {
+            return Err(Error::InvalidParameter("The channel doesn't exist."));
+        }
+
+        let frame_size = self.header.frame_size();
+        let sample_size = self.header.sample_size();
+        let offset = channel as usize * sample_size;
+
+        let mut buf = vec![0u8; frame_size * 4096];
+        let mut samples = Vec::with_capacity(sample_size * 4096);
+        let mut filled = 0;
+        let mut written = 0;
+        while self.remaining > 0 {
+            let end = buf
+                .len()
+                .min(filled + self.remaining.min(usize::MAX as u64) as usize);
+            let read = match self.reader.read(&mut buf[filled..end]) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            };
+            self.remaining -= read as u64;
+            filled += read;
+
+            let whole = filled - filled % frame_size;
+            samples.clear();
+            for frame in buf[..whole].chunks_exact(frame_size) {
+                samples.extend_from_slice(&frame[offset..offset + sample_size]);
+            }
+            writer.write_all(&samples)?;
+            written += (whole / frame_size) as u64;
+
+            buf.copy_within(whole..filled, 0);
+            filled -= whole;
+        }
+
+        Ok(written)
+    }
+
+    /// Parses the contents of the format chunk.
+    fn parse_fmt(fmt: &[u8]) -> Result<WavHeader, Error> {
+        if fmt.len() < 16 {
+            return Err(Error::InvalidFormat("The format chunk is too short."));
+        }
+
+        let u16_at = |offset: usize| u16::from_le_bytes([fmt[offset], fmt[offset + 1]]);
+        let tag = match u16_at(0) {
+            0xfffe if fmt.len() >= 26 => u16_at(24),
+            tag => tag,
+        };
+        let format = match tag {
+            1 => WavFormat::Pcm,
+            3 => WavFormat::Float,
+            _ => return Err(Error::InvalidFormat("Unsupported sample encoding.")),
+        };
+
+        let header = WavHeader {
+            format,
+            channels: u16_at(2),
+            sample_rate: u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]),
+            bits_per_sample: u16_at(14),
+        };
+        if header.channels == 0 || header.bits_per_sample == 0 || header.sample_size() > 8 {
+            return Err(Error::InvalidFormat("Invalid sample layout."));
+        }
+
+        Ok(header)
+    }
+
+    /// Skips the number of bytes of the reader.
+    fn skip(reader: &mut R, bytes: u64) -> Result<(), Error> {
+        let skipped = std::io::copy(&mut reader.by_ref().take(bytes), &mut std::io::sink())?;
+        if skipped < bytes {
+            return Err(Error::InvalidFormat("The file is truncated."));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Statistics;
+
+    fn wav(format: u16, channels: u16, bits: u16, data: &[u8]) -> Vec<u8> {
+        let mut wav = b"RIFF\0\0\0\0WAVE".to_vec();
+        wav.extend_from_slice(b"LIST\x03\0\0\0abc\0");
+        wav.extend_from_slice(b"fmt \x10\0\0\0");
+        wav.extend_from_slice(&format.to_le_bytes());
+        wav.extend_from_slice(&channels.to_le_bytes());
+        wav.extend_from_slice(&8000u32.to_le_bytes());
+        wav.extend_from_slice(&0u32.to_le_bytes());
+        wav.extend_from_slice(&(channels * bits / 8).to_le_bytes());
+        wav.extend_from_slice(&bits.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        wav.extend_from_slice(data);
+        wav
+    }
+
+    #[test]
+    fn caps_format_chunk() {
+        let mut huge = wav(1, 1, 16, &[1, 0, 3, 0]);
+        // a format chunk claiming 4 GiB, followed by the usual 16 bytes only
+        huge[28..32].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(WavReader::new(&huge[..]).is_err());
+
+        let mut padded = wav(1, 1, 16, &[1, 0, 3, 0]);
+        padded[28..32].copy_from_slice(&50u32.to_le_bytes());
+        padded.splice(48..48, [0u8; 34].iter().copied());
+        let mut reader = WavReader::new(&padded[..]).unwrap();
+        let mut roller = reader.header().pcm_window::<4, f32>().unwrap();
+        assert_eq!(reader.copy_channel(0, &mut roller).unwrap(), 2);
+        assert_eq!(roller.mean(), 2.0);
+    }
+
+    #[test]
+    fn reads_pcm_channel() {
+        let data = [1, 0, 0, 0xfe, 0xff, 0xff, 3, 0, 0, 0, 0, 0, 5, 0];
+        let wav = wav(1, 2, 24, &data);
+        let mut reader = WavReader::new(&wav[..]).unwrap();
+        assert_eq!(reader.header().sample_rate, 8000);
+        assert_eq!(reader.header().frame_size(), 6);
+
+        let mut roller = reader.header().pcm_window::<4, f32>().unwrap();
+        assert_eq!(reader.copy_channel(1, &mut roller).unwrap(), 2);
+        assert_eq!(roller.buffer, vec![-2, 0]);
+    }
+
+    #[test]
+    fn reads_float_channel() {
+        let data: Vec<u8> = [0.5f32, 1.5].iter().flat_map(|v| v.to_le_bytes()).collect();
+        let wav = wav(3, 1, 32, &data);
+        let mut reader = WavReader::new(&wav[..]).unwrap();
+        assert!(reader.header().pcm_window::<4, f32>().is_err());
+
+        let mut roller = RollingStats::<f32, LittleEndian, 4>::new();
+        reader.copy_channel(0, &mut roller).unwrap();
+        assert_eq!(roller.mean(), 1.0);
+        assert!(matches!(
+            reader.copy_channel(1, &mut roller),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_other_files() {
+        assert!(matches!(
+            WavReader::new(&b"RIFF\0\0\0\0AVI LIST"[..]),
+            Err(Error::InvalidFormat(_))
+        ));
+    }
+}
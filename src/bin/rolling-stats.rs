@@ -0,0 +1,264 @@
+//! Prints the rolling statistics of raw data read from a file or the standard input, such as to inspect a capture.
+//!
+//! Usage: `rolling-stats [--type i32|f32|f64] [--endian little|big|native] [--window N] [--every N] [FILE]`
+
+use rolling_stats::{
+    BigEndian, ConverterFromRaw, DynRollingStats, LittleEndian, NativeEndian, Statistics,
+};
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    process::exit,
+};
+
+const USAGE: &str = "Usage: rolling-stats [OPTIONS] [FILE]
+
+Reads the raw data from the FILE, or the standard input if no FILE or - is given,
+and prints the statistics of the rolling window.
+
+Options:
+  --type TYPE      the type of the values: i32, f32 or f64 (default: i32)
+  --endian ORDER   the byte order of the values: little, big or native (default: little)
+  --window N       the number of values in the window (default: 100)
+  --every N        print the statistics after every N values and at the end of the input,
+                   instead of after every chunk read
+  --help           print this help";
+
+/// The size of the chunks the input is read in.
+const CHUNK_SIZE: usize = 8192;
+
+/// The options given on the command line.
+struct Options {
+    value_type: String,
+    endian: String,
+    window: usize,
+    every: Option<usize>,
+    path: Option<String>,
+    help: bool,
+}
+
+/// Parses the command line arguments.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        value_type: "i32".to_string(),
+        endian: "little".to_string(),
+        window: 100,
+        every: None,
+        path: None,
+        help: false,
+    };
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value of {}", name));
+        let parse = |name: &str, value: String| {
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("invalid value of {}: {}", name, value))
+        };
+
+        match arg.as_str() {
+            "--type" => options.value_type = value("--type")?,
+            "--endian" => options.endian = value("--endian")?,
+            "--window" => options.window = parse("--window", value("--window")?)?,
+            "--every" => {
+                options.every = Some(parse("--every", value("--every")?)?).filter(|n| *n > 0)
+            }
+            "--help" | "-h" => options.help = true,
+            "-" => options.path = None,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ => options.path = Some(arg),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Feeds the input into a window of the values of type `T` parsed using the converter `E`, printing the statistics to the output.
+fn run<T, E>(
+    options: &Options,
+    mut input: impl Read,
+    output: &mut impl Write,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    T: Copy + Default + PartialOrd + std::ops::Add<T, Output = T>,
+    E: ConverterFromRaw<T>,
+    DynRollingStats<T, E, f64>: Statistics<f64> + std::fmt::Display,
+{
+    let mut roller = DynRollingStats::<T, E, f64>::new_with_window(options.window);
+    let period = options.every.map(|every| every * std::mem::size_of::<T>());
+    let mut buf = vec![0; CHUNK_SIZE];
+    let mut since_print = 0;
+
+    loop {
+        let read = match input.read(&mut buf) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into()),
+        };
+
+        match period {
+            Some(period) => {
+                let mut chunk = &buf[..read];
+                while !chunk.is_empty() {
+                    let len = chunk.len().min(period - since_print);
+                    roller.write_all(&chunk[..len])?;
+                    chunk = &chunk[len..];
+                    since_print += len;
+                    if since_print == period {
+                        writeln!(output, "{}", roller)?;
+                        since_print = 0;
+                    }
+                }
+            }
+            None => {
+                roller.write_all(&buf[..read])?;
+                writeln!(output, "{}", roller)?;
+            }
+        }
+    }
+
+    // the values following the last period are summarized at the end of the input
+    if since_print >= std::mem::size_of::<T>() {
+        writeln!(output, "{}", roller)?;
+    }
+    if roller.pending_bytes() > 0 {
+        eprintln!(
+            "warning: {} trailing bytes don't form a whole value",
+            roller.pending_bytes()
+        );
+    }
+
+    Ok(())
+}
+
+/// Selects the converter of the byte order.
+fn run_type<T>(
+    options: &Options,
+    input: impl Read,
+    output: &mut impl Write,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    T: Copy + Default + PartialOrd + std::ops::Add<T, Output = T>,
+    LittleEndian: ConverterFromRaw<T>,
+    BigEndian: ConverterFromRaw<T>,
+    NativeEndian: ConverterFromRaw<T>,
+    DynRollingStats<T, LittleEndian, f64>: Statistics<f64> + std::fmt::Display,
+    DynRollingStats<T, BigEndian, f64>: Statistics<f64> + std::fmt::Display,
+    DynRollingStats<T, NativeEndian, f64>: Statistics<f64> + std::fmt::Display,
+{
+    match options.endian.as_str() {
+        "little" => run::<T, LittleEndian>(options, input, output),
+        "big" => run::<T, BigEndian>(options, input, output),
+        "native" => run::<T, NativeEndian>(options, input, output),
+        endian => Err(format!("unknown byte order {}", endian).into()),
+    }
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("error: {}\n\n{}", error, USAGE);
+            exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        exit(0);
+    }
+
+    let input: Box<dyn Read> = match &options.path {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(file),
+            Err(error) => {
+                eprintln!("error: can't open {}: {}", path, error);
+                exit(1);
+            }
+        },
+        None => Box::new(io::stdin()),
+    };
+
+    let output = &mut io::stdout();
+    let result = match options.value_type.as_str() {
+        "i32" => run_type::<i32>(&options, input, output),
+        "f32" => run_type::<f32>(&options, input, output),
+        "f64" => run_type::<f64>(&options, input, output),
+        value_type => Err(format!("unknown type {}", value_type).into()),
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter()
+            .map(|arg| arg.to_string())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    #[test]
+    fn parses_args() {
+        let options = parse_args(args(&[
+            "--type",
+            "f32",
+            "--endian",
+            "big",
+            "--window",
+            "5",
+            "--every",
+            "2",
+            "capture.bin",
+        ]))
+        .unwrap();
+        assert_eq!(options.value_type, "f32");
+        assert_eq!(options.endian, "big");
+        assert_eq!(options.window, 5);
+        assert_eq!(options.every, Some(2));
+        assert_eq!(options.path.as_deref(), Some("capture.bin"));
+        assert!(!options.help);
+
+        let options = parse_args(args(&["--every", "0", "-"])).unwrap();
+        assert_eq!(options.every, None);
+        assert_eq!(options.path, None);
+        assert!(parse_args(args(&["--help"])).unwrap().help);
+    }
+
+    #[test]
+    fn rejects_invalid_args() {
+        assert_eq!(
+            parse_args(args(&["--bogus"])).err().unwrap(),
+            "unknown option --bogus"
+        );
+        assert_eq!(
+            parse_args(args(&["--window", "x"])).err().unwrap(),
+            "invalid value of --window: x"
+        );
+        assert_eq!(
+            parse_args(args(&["--type"])).err().unwrap(),
+            "missing value of --type"
+        );
+    }
+
+    #[test]
+    fn prints_every_period() {
+        let options = parse_args(args(&["--endian", "big", "--every", "2"])).unwrap();
+        // three values followed by a partial one
+        let input = [0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 6, 0, 0];
+        let mut output = Vec::new();
+        run_type::<i32>(&options, &input[..], &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("n=2 mean=1.5 "));
+        assert!(lines[1].starts_with("n=3 mean=3 "));
+    }
+}
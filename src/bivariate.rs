@@ -0,0 +1,185 @@
+//! Statistics relating the window to another, parallel window of data.
+//!
+//! The windows are expected to be aligned - the n-th items of both windows are treated as a pair of observations.
+
+use crate::{convertfloat::LossyFloatConvertible, RollingStats, WindowStorage};
+use alloc::{vec, vec::Vec};
+use core::{cmp::Ordering, ops::Add};
+use rand_distr::num_traits::Float;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns the regression coefficient of the window against a reference window, `cov(self, reference) / var(reference)`.
+    /// All of the items are weighted equally, the time decay of the windows is ignored.
+    /// # Returns
+    /// Returns `None` when the windows differ in length, hold less than two items or when the variance of the reference is zero.
+    pub fn beta(&self, reference: &RollingStats<T, E, WINDOW_SIZE, O, S>) -> Option<O> {
+        let covariance = self.covariance(reference)?;
+        let variance = reference.covariance(reference)?;
+        if variance.is_zero() {
+            return None;
+        }
+
+        Some(covariance / variance)
+    }
+
+    /// Returns the sample covariance of the window and the other window,
+    /// or `None` when the windows differ in length or hold less than two items.
+    /// Computed around the arithmetic means, so that the time decay does not skew the unweighted sum.
+    fn covariance(&self, other: &RollingStats<T, E, WINDOW_SIZE, O, S>) -> Option<O> {
+        if self.len() != other.len() || self.len() < 2 {
+            return None;
+        }
+
+        let mean = self.arithmetic_mean();
+        let other_mean = other.arithmetic_mean();
+
+        let sum = self
+            .buffer
+            .iter()
+            .zip(other.buffer.iter())
+            .fold(O::zero(), |acc, (x, y)| {
+                acc + (x.convert() - mean) * (y.convert() - other_mean)
+            });
+
+        Some(sum / O::from(self.len() - 1).unwrap())
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: PartialOrd,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns the Spearman's rank correlation of the window and the other window,
+    /// i.e. the Pearson correlation of the ranks of the values in the windows.
+    /// Tied values are assigned the average of the ranks they span, the time decay of the windows is ignored.
+    /// # Returns
+    /// Returns `None` when the windows differ in length, hold less than two items or when either of the windows is constant.
+    pub fn spearman(&self, other: &RollingStats<T, E, WINDOW_SIZE, O, S>) -> Option<O> {
+        if self.len() != other.len() || self.len() < 2 {
+            return None;
+        }
+
+        pearson(&ranks(self.buffer.iter()), &ranks(other.buffer.iter()))
+    }
+}
+
+/// Returns the ranks of the values, starting from one, tied values get the average of their ranks.
+fn ranks<'a, T, O>(values: impl IntoIterator<Item = &'a T>) -> Vec<O>
+where
+    T: PartialOrd + 'a,
+    O: Float,
+{
+    let values: Vec<&T> = values.into_iter().collect();
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|a, b| {
+        values[*a]
+            .partial_cmp(values[*b])
+            .unwrap_or(Ordering::Equal)
+    });
+
+    let mut ranks = vec![O::zero(); values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+
+        // average of the one-based ranks start + 1 ..= end
+        let rank = O::from(start + end + 1).unwrap() / O::from(2).unwrap();
+        for index in &order[start..end] {
+            ranks[*index] = rank;
+        }
+        start = end;
+    }
+
+    ranks
+}
+
+/// Returns the Pearson correlation of two equally long series, or `None` if either of them is constant.
+fn pearson<O: Float>(x: &[O], y: &[O]) -> Option<O> {
+    let n = O::from(x.len()).unwrap();
+    let x_mean = x.iter().fold(O::zero(), |acc, v| acc + *v) / n;
+    let y_mean = y.iter().fold(O::zero(), |acc, v| acc + *v) / n;
+
+    let (covariance, x_variance, y_variance) = x.iter().zip(y.iter()).fold(
+        (O::zero(), O::zero(), O::zero()),
+        |(covariance, x_variance, y_variance), (x, y)| {
+            let (dx, dy) = (*x - x_mean, *y - y_mean);
+            (
+                covariance + dx * dy,
+                x_variance + dx * dx,
+                y_variance + dy * dy,
+            )
+        },
+    );
+
+    if x_variance.is_zero() || y_variance.is_zero() {
+        return None;
+    }
+
+    Some(covariance / (x_variance * y_variance).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LittleEndian, RollingStats};
+    use approx::*;
+
+    fn roller(data: &[i32]) -> RollingStats<i32, LittleEndian, 4> {
+        let mut roller = RollingStats::default();
+        for value in data {
+            let _ = roller.feed(&value.to_le_bytes()).unwrap();
+        }
+        roller
+    }
+
+    #[test]
+    fn beta() {
+        let reference = roller(&[1, 2, 3, 4]);
+        let signal = roller(&[3, 5, 7, 9]);
+
+        assert_abs_diff_eq!(signal.beta(&reference).unwrap(), 2.0);
+        assert_abs_diff_eq!(reference.beta(&signal).unwrap(), 0.5);
+    }
+
+    #[test]
+    fn beta_ignores_time_decay() {
+        let reference = roller(&[1, 2, 3, 4]).with_time_decay(0.5);
+        let signal = roller(&[3, 5, 7, 9]).with_time_decay(0.5);
+
+        assert_abs_diff_eq!(signal.beta(&reference).unwrap(), 2.0);
+    }
+
+    #[test]
+    fn beta_undefined() {
+        let signal = roller(&[1, 2, 3, 4]);
+
+        assert!(signal.beta(&roller(&[1, 2, 3])).is_none());
+        assert!(signal.beta(&roller(&[5, 5, 5, 5])).is_none());
+        assert!(roller(&[1]).beta(&roller(&[2])).is_none());
+    }
+
+    #[test]
+    fn spearman() {
+        let signal = roller(&[1, 2, 3, 4]);
+
+        assert_abs_diff_eq!(signal.spearman(&roller(&[1, 8, 27, 64])).unwrap(), 1.0);
+        assert_abs_diff_eq!(signal.spearman(&roller(&[9, 4, 1, 0])).unwrap(), -1.0);
+        // ranks of [1, 1, 2, 3] are [1.5, 1.5, 3, 4]
+        assert_abs_diff_eq!(
+            signal.spearman(&roller(&[1, 1, 2, 3])).unwrap(),
+            0.948_683_3,
+            epsilon = 1e-6
+        );
+        assert!(signal.spearman(&roller(&[2, 2, 2, 2])).is_none());
+        assert!(signal.spearman(&roller(&[1, 2])).is_none());
+    }
+}
@@ -0,0 +1,171 @@
+//! A builder configuring the window in one place, including the type of the converter and the runtime window size.
+
+use crate::{
+    Decoder, EvictionPolicy, Filter, ReconstructionStrategy, RollingStats, Transform, WindowStorage,
+};
+use alloc::{collections::VecDeque, sync::Arc};
+use core::marker::PhantomData;
+
+/// Configures and creates a `RollingStats`.
+/// The options are the same as the ones set using the `with_*` methods of the `RollingStats`,
+/// the converter `E` can be chosen using the `with_endianness` method, the size of a runtime-sized window using the `with_window_size` method.
+///
+/// # Example
+/// ```
+/// use rolling_stats::{BigEndian, DynRollingStats, EvictionPolicy, Statistics};
+///
+/// let mut roller = DynRollingStats::<i32, BigEndian>::builder()
+///     .with_window_size(2)
+///     .with_eviction_policy(EvictionPolicy::Evict)
+///     .with_filter(|value| *value >= 0)
+///     .with_ddof(0)
+///     .build();
+/// roller.feed(&[0, 0, 0, 1, 255, 255, 255, 255, 0, 0, 0, 3]).unwrap();
+///
+/// assert_eq!(roller.mean(), 2.0);
+/// assert_eq!(roller.std_dev(), 1.0);
+/// ```
+pub struct RollingStatsBuilder<T, E, const WINDOW_SIZE: usize, O = f32, S = VecDeque<T>> {
+    _types: PhantomData<(E, O, S)>,
+    window_size: usize,
+    ddof: usize,
+    eviction_policy: EvictionPolicy,
+    strict_alignment: Option<usize>,
+    strategy: ReconstructionStrategy,
+    transform: Option<Transform<T>>,
+    filter: Option<Filter<T>>,
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStatsBuilder<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Creates a builder of a window with the default options.
+    pub fn new() -> Self {
+        Self {
+            _types: PhantomData,
+            window_size: WINDOW_SIZE,
+            ddof: 1,
+            eviction_policy: EvictionPolicy::default(),
+            strict_alignment: None,
+            strategy: ReconstructionStrategy::default(),
+            transform: None,
+            filter: None,
+        }
+    }
+
+    /// Sets the converter of the raw data, such as `BigEndian` or `LittleEndian`.
+    pub fn with_endianness<C>(self) -> RollingStatsBuilder<T, C, WINDOW_SIZE, O, S> {
+        RollingStatsBuilder {
+            _types: PhantomData,
+            window_size: self.window_size,
+            ddof: self.ddof,
+            eviction_policy: self.eviction_policy,
+            strict_alignment: self.strict_alignment,
+            strategy: self.strategy,
+            transform: self.transform,
+            filter: self.filter,
+        }
+    }
+
+    /// Sets the delta degrees of freedom of the standard deviation, see `RollingStats::with_ddof`.
+    pub fn with_ddof(mut self, ddof: usize) -> Self {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Sets the policy applied once the window is full, see `RollingStats::with_eviction_policy`.
+    pub fn with_eviction_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.eviction_policy = policy;
+        self
+    }
+
+    /// Enables the strict alignment mode, see `RollingStats::with_strict_alignment`.
+    pub fn with_strict_alignment(mut self, max_partial_writes: usize) -> Self {
+        self.strict_alignment = Some(max_partial_writes);
+        self
+    }
+
+    /// Sets the strategy of reconstructing the incomplete raw data, see `RollingStats::with_reconstruction_strategy`.
+    pub fn with_reconstruction_strategy(mut self, strategy: ReconstructionStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Sets a transformation applied to every parsed value, see `RollingStats::with_transform`.
+    pub fn with_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Sets a predicate deciding whether a parsed value enters the window, see `RollingStats::with_filter`.
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Creates the configured window, the window size is limited to the capacity of a fixed capacity storage.
+    pub fn build(self) -> RollingStats<T, E, WINDOW_SIZE, O, S> {
+        let mut stats = RollingStats::new();
+        stats.window_size = stats.fitting_window_size(self.window_size);
+        stats.ddof = self.ddof;
+        stats.eviction_policy = self.eviction_policy;
+        stats.strict_alignment = self.strict_alignment;
+        stats.decoder = Decoder::new(self.strategy, core::mem::size_of::<T>());
+        stats.transform = self.transform;
+        stats.filter = self.filter;
+        stats
+    }
+}
+
+impl<T, E, O, S> RollingStatsBuilder<T, E, 0, O, S> {
+    /// Sets the size of the runtime-sized window.
+    pub fn with_window_size(mut self, window_size: usize) -> Self {
+        self.window_size = window_size;
+        self
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> Default for RollingStatsBuilder<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Returns a builder of the window.
+    pub fn builder() -> RollingStatsBuilder<T, E, WINDOW_SIZE, O, S> {
+        RollingStatsBuilder::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LittleEndian, NativeEndian, ReconstructionStrategy, RollingStatsBuilder};
+    use alloc::vec;
+
+    #[test]
+    fn builds_configured_window() {
+        let mut roller = RollingStatsBuilder::<i32, NativeEndian, 3>::new()
+            .with_endianness::<LittleEndian>()
+            .with_transform(|value| value * 2)
+            .with_strict_alignment(0)
+            .with_reconstruction_strategy(ReconstructionStrategy::Reconstructor)
+            .build();
+        roller.feed(&[1, 0, 0, 0, 2, 0, 0, 0]).unwrap();
+        assert_eq!(roller.buffer, vec![2, 4]);
+        assert!(roller.feed(&[3]).is_err());
+    }
+}
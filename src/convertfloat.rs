@@ -0,0 +1,57 @@
+//! Integer types in Rust do not allow for converting them into floats using the `From/Into` traits
+//! as these conversions are lossy.
+//!
+//! The following trait allows for workarounding this problem via a custom trait.
+//! `O` denotes the float type the value is converted into, which allows choosing the precision of the statistics.
+pub trait LossyFloatConvertible<O> {
+    /// Convert `self` into a float.
+    fn convert(&self) -> O;
+}
+
+impl LossyFloatConvertible<f32> for i32 {
+    fn convert(&self) -> f32 {
+        *self as f32
+    }
+}
+
+impl LossyFloatConvertible<f64> for i32 {
+    fn convert(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl LossyFloatConvertible<f32> for i64 {
+    fn convert(&self) -> f32 {
+        *self as f32
+    }
+}
+
+impl LossyFloatConvertible<f64> for i64 {
+    fn convert(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl LossyFloatConvertible<f32> for f32 {
+    fn convert(&self) -> f32 {
+        *self
+    }
+}
+
+impl LossyFloatConvertible<f64> for f32 {
+    fn convert(&self) -> f64 {
+        *self as f64
+    }
+}
+
+impl LossyFloatConvertible<f32> for f64 {
+    fn convert(&self) -> f32 {
+        *self as f32
+    }
+}
+
+impl LossyFloatConvertible<f64> for f64 {
+    fn convert(&self) -> f64 {
+        *self
+    }
+}
@@ -0,0 +1,160 @@
+//! Time-decay weighting of the window, so that the newest items dominate the statistics without shrinking the window.
+
+use crate::{convertfloat::LossyFloatConvertible, Error, RollingStats, WindowStorage};
+use rand_distr::num_traits::Float;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + LossyFloatConvertible<O>,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Weights the items of the window by their age when computing the mean and the standard deviation,
+    /// the newest item has the weight of one, every older item has the weight of the following item multiplied by `decay`.
+    /// The `decay` has to be in the `(0, 1]` range, one weights all of the items equally.
+    ///
+    /// The window still evicts the items exceeding the window size, combining the sharp cutoff with the emphasis on the recent items.
+    /// The standard deviation is computed using the reliability weights, the divisor is `V1 - ddof * V2 / V1`,
+    /// where `V1` is the sum of the weights and `V2` the sum of the squared weights.
+    ///
+    /// The decay applies to the `mean` and the `std_dev` and to everything derived from the two,
+    /// such as the reports including the `windows`, the tumbling windows and the `MultiResolutionStats`, the sampling, the alarms and the anomaly detection.
+    /// The `beta` and the `spearman` correlation weight all of the items equally.
+    /// # Panics
+    /// Panics if the decay is not in the `(0, 1]` range.
+    pub fn with_time_decay(self, decay: O) -> Self {
+        self.try_with_time_decay(decay)
+            .expect("The decay has to be in the (0, 1] range.")
+    }
+
+    /// Weights the items of the window by their age, the same way as `with_time_decay`.
+    /// # Errors
+    /// Returns the `Error::InvalidParameter` error if the decay is not in the `(0, 1]` range.
+    pub fn try_with_time_decay(mut self, decay: O) -> Result<Self, Error> {
+        if !(decay > O::zero() && decay <= O::one()) {
+            return Err(Error::InvalidParameter(
+                "The decay has to be in the (0, 1] range.",
+            ));
+        }
+
+        self.decay = Some(decay);
+        Ok(self)
+    }
+
+    /// Returns the pairs of the weight and the value of the items, the newest first.
+    fn weighted(&self, decay: O) -> impl Iterator<Item = (O, O)> + '_ {
+        self.buffer
+            .iter()
+            .rev()
+            .scan(O::one(), move |weight, item| {
+                let current = *weight;
+                *weight = *weight * decay;
+                Some((current, item.convert()))
+            })
+    }
+
+    /// Returns the mean of the items weighted by their age, zero for an empty window.
+    pub(crate) fn decayed_mean(&self, decay: O) -> O {
+        let (sum, weights) = self
+            .weighted(decay)
+            .fold((O::zero(), O::zero()), |(sum, weights), (weight, value)| {
+                (sum + weight * value, weights + weight)
+            });
+
+        if weights > O::zero() {
+            sum / weights
+        } else {
+            O::zero()
+        }
+    }
+
+    /// Returns the standard deviation of the items weighted by their age.
+    pub(crate) fn decayed_std_dev(&self, decay: O) -> O {
+        let mean = self.decayed_mean(decay);
+        let (sum, v1, v2) = self.weighted(decay).fold(
+            (O::zero(), O::zero(), O::zero()),
+            |(sum, v1, v2), (weight, value)| {
+                (
+                    sum + weight * (value - mean).powi(2),
+                    v1 + weight,
+                    v2 + weight * weight,
+                )
+            },
+        );
+
+        if v1 <= O::zero() {
+            return O::zero();
+        }
+
+        let divisor = v1 - O::from(self.ddof).unwrap() * v2 / v1;
+        // a window too short for the delta degrees of freedom, the same way the unweighted divisor is at least one
+        let divisor = if divisor > O::zero() { divisor } else { v1 };
+        (sum / divisor).sqrt()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, Error, RollingStats, Statistics};
+    use alloc::vec;
+    use approx::*;
+
+    #[test]
+    fn time_decay() {
+        let values = [1, 2, 3, 4];
+        let plain: RollingStats<i32, BigEndian, 4> = values.iter().copied().collect();
+        let uniform = plain.clone().with_time_decay(1.0);
+        assert_abs_diff_eq!(uniform.mean(), plain.mean());
+        assert_abs_diff_eq!(uniform.std_dev(), plain.std_dev(), epsilon = 1e-6);
+
+        let mut decayed = RollingStats::<i32, BigEndian, 4>::new()
+            .with_time_decay(0.5)
+            .with_ddof(0);
+        decayed.extend(values.iter().copied());
+        // weights 1/8, 1/4, 1/2, 1 of the sum 15/8
+        assert_abs_diff_eq!(decayed.mean(), 49.0 / 15.0, epsilon = 1e-6);
+        let mean = decayed.mean();
+        let variance = [(1.0, 0.125), (2.0, 0.25), (3.0, 0.5), (4.0, 1.0)]
+            .iter()
+            .map(|(value, weight)| weight * (value - mean) * (value - mean))
+            .sum::<f32>()
+            / 1.875;
+        assert_abs_diff_eq!(decayed.std_dev(), variance.sqrt(), epsilon = 1e-6);
+
+        decayed.clear();
+        assert_eq!(decayed.mean(), 0.0);
+        assert_eq!(decayed.std_dev(), 0.0);
+    }
+
+    #[test]
+    fn decayed_reports() {
+        let mut decayed = RollingStats::<i32, BigEndian, 4>::new()
+            .with_time_decay(0.5)
+            .with_tumbling_window(4, 1);
+        decayed.extend(1..=4);
+
+        let report = decayed.report();
+        assert_abs_diff_eq!(report.mean, 49.0 / 15.0, epsilon = 1e-6);
+        assert_eq!(decayed.windows(4).next(), Some(report));
+        assert_eq!(decayed.take_tumbling_reports(), vec![report]);
+    }
+
+    #[test]
+    fn time_decay_bounds() {
+        let stats = || RollingStats::<i32, BigEndian, 4>::new();
+        assert!(stats().try_with_time_decay(1.0).is_ok());
+        assert!(stats().try_with_time_decay(f32::MIN_POSITIVE).is_ok());
+        for decay in [0.0, -0.5, 1.5, f32::NAN, f32::INFINITY] {
+            assert!(matches!(
+                stats().try_with_time_decay(decay),
+                Err(Error::InvalidParameter(_))
+            ));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn time_decay_out_of_range() {
+        let _ = RollingStats::<i32, BigEndian, 4>::new().with_time_decay(0.0);
+    }
+}
@@ -0,0 +1,110 @@
+//! A window whose size is set at runtime, such as when loaded from a configuration, rather than by the `WINDOW_SIZE` constant.
+
+use crate::{Error, RollingStats, WindowStorage};
+use alloc::collections::VecDeque;
+
+/// A window sized at runtime using the `new_with_window` constructor, providing the same API as the `RollingStats`.
+/// The `WINDOW_SIZE` constant of the window is zero, the actual size is returned by the `window_size` method.
+///
+/// The `new` and `default` constructors create a window of size zero, which discards every value until resized using the `resize` method.
+/// Collecting an iterator sizes the window to the number of the values.
+pub type DynRollingStats<T, E, O = f32, S = VecDeque<T>> = RollingStats<T, E, 0, O, S>;
+
+impl<T, E, O, S> RollingStats<T, E, 0, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Creates a new instance of the `RollingStats` with empty buffer, holding at most `window_size` items.
+    /// The window size is limited to the capacity of a fixed capacity storage.
+    pub fn new_with_window(window_size: usize) -> Self {
+        let mut stats = Self::new();
+        stats.window_size = stats.fitting_window_size(window_size);
+        stats
+    }
+
+    /// Changes the size of the window, evicting the oldest items exceeding the new size the same way as when pushing values,
+    /// so that the statistics, the value counts and the eviction sink remain consistent.
+    /// Growing the window keeps the items, the window fills up with the following values.
+    /// # Errors
+    /// Returns the `Error::InvalidParameter` error if the size exceeds the capacity of a fixed capacity storage, keeping the window intact,
+    /// or the error of the eviction sink, in which case the items not yet evicted remain in the window.
+    pub fn resize(&mut self, window_size: usize) -> Result<(), Error>
+    where
+        T: Copy,
+    {
+        if self.fitting_window_size(window_size) < window_size {
+            return Err(Error::InvalidParameter(
+                "window size exceeds the storage capacity",
+            ));
+        }
+
+        self.window_size = window_size;
+        self.evict(window_size)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{ArrayDeque, DynRollingStats, Error, LittleEndian, RollingStats, Statistics};
+    use std::io::Write;
+
+    #[test]
+    fn runtime_sized_window() {
+        let mut roller = DynRollingStats::<i32, LittleEndian>::new_with_window(3);
+        let mut fixed = RollingStats::<i32, LittleEndian, 3>::new();
+        for value in [1i32, 5, 2, 8, 4] {
+            roller.write_all(&value.to_le_bytes()).unwrap();
+            fixed.write_all(&value.to_le_bytes()).unwrap();
+        }
+
+        assert_eq!(roller.window_size(), 3);
+        assert_eq!(roller.buffer, vec![2, 8, 4]);
+        assert_eq!(roller.mean(), fixed.mean());
+        assert_eq!(roller.std_dev(), fixed.std_dev());
+        assert_eq!(roller.to_f32_window().window_size(), 3);
+    }
+
+    #[test]
+    fn collected_window() {
+        let roller: DynRollingStats<i32, LittleEndian> = (1..=4).collect();
+        assert_eq!(roller.window_size(), 4);
+        assert_eq!(roller.mean(), 2.5);
+
+        let fixed: DynRollingStats<i32, LittleEndian, f32, ArrayDeque<i32, 2>> = (1..=4).collect();
+        assert_eq!(fixed.window_size(), 2);
+        assert_eq!(fixed.buffer, vec![3, 4]);
+    }
+
+    #[test]
+    fn resize() {
+        let mut roller =
+            DynRollingStats::<i32, LittleEndian>::new_with_window(4).with_mode_tracking();
+        for value in [1i32, 2, 2, 3] {
+            roller.write_all(&value.to_le_bytes()).unwrap();
+        }
+
+        roller.resize(2).unwrap();
+        assert_eq!(roller.buffer, vec![2, 3]);
+        assert_eq!(roller.evicted(), 2);
+        assert_eq!(roller.unique_count(), 2);
+        assert_eq!(roller.mean(), 2.5);
+
+        roller.resize(3).unwrap();
+        roller.write_all(&4i32.to_le_bytes()).unwrap();
+        roller.write_all(&5i32.to_le_bytes()).unwrap();
+        assert_eq!(roller.buffer, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn resize_beyond_capacity() {
+        let mut roller =
+            DynRollingStats::<i32, LittleEndian, f32, ArrayDeque<i32, 3>>::new_with_window(2);
+        roller.extend([1, 2]);
+
+        assert!(matches!(roller.resize(5), Err(Error::InvalidParameter(_))));
+        assert_eq!(roller.window_size(), 2);
+        roller.resize(3).unwrap();
+        roller.extend([3, 4]);
+        assert_eq!(roller.buffer, vec![2, 3, 4]);
+    }
+}
@@ -0,0 +1,103 @@
+//! The error type of the crate, unifying the failures of the data ingestion and of the statistics computation.
+
+use crate::RawConversionError;
+use alloc::string::String;
+#[cfg(feature = "std")]
+use thiserror::Error;
+
+/// An Error returned by the `RollingStats` methods.
+/// The methods of the `std::io::Write` trait return the error converted into the `std::io::Error`,
+/// the original error can be retrieved using the `std::io::Error::get_ref` method.
+#[derive(Debug)]
+#[cfg_attr(feature = "std", derive(Error))]
+pub enum Error {
+    #[cfg_attr(feature = "std", error("Data conversion failed: {0}"))]
+    Conversion(RawConversionError),
+    #[cfg_attr(feature = "std", error("Misaligned data received."))]
+    Misaligned,
+    #[cfg_attr(feature = "std", error("Non-finite value received."))]
+    NonFinite,
+    #[cfg_attr(
+        feature = "std",
+        error("Invalid text encoding of the raw data received.")
+    )]
+    InvalidEncoding,
+    #[cfg_attr(feature = "std", error("The window contains no values."))]
+    EmptyWindow,
+    #[cfg_attr(feature = "std", error("The window is full and rejects new values."))]
+    WindowFull,
+    #[cfg_attr(feature = "std", error("Invalid parameter: {0}"))]
+    InvalidParameter(&'static str),
+    #[cfg_attr(feature = "std", error("Invalid format of the input: {0}"))]
+    InvalidFormat(&'static str),
+    #[cfg_attr(feature = "std", error("No metric named {0} is registered."))]
+    UnknownMetric(String),
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    Io(std::io::Error),
+}
+
+impl From<RawConversionError> for Error {
+    fn from(error: RawConversionError) -> Self {
+        Error::Conversion(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    /// Wraps the `std::io::Error` into the `Error::Io` variant,
+    /// unless it was converted from the `Error` in the first place, in which case the original error is returned.
+    fn from(error: std::io::Error) -> Self {
+        match error.get_ref().map(|inner| inner.is::<Error>()) {
+            Some(true) => *error.into_inner().unwrap().downcast::<Error>().unwrap(),
+            _ => Error::Io(error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    /// Converts the error into the `std::io::Error`, keeping the original error as the inner error.
+    /// The `Error::Io` variant is unwrapped instead.
+    fn from(error: Error) -> Self {
+        let kind = match error {
+            Error::Io(error) => return error,
+            Error::Conversion(_)
+            | Error::Misaligned
+            | Error::NonFinite
+            | Error::InvalidEncoding
+            | Error::InvalidFormat(_) => std::io::ErrorKind::InvalidData,
+            Error::EmptyWindow | Error::WindowFull => std::io::ErrorKind::Other,
+            Error::InvalidParameter(_) => std::io::ErrorKind::InvalidInput,
+            Error::UnknownMetric(_) => std::io::ErrorKind::NotFound,
+        };
+
+        std::io::Error::new(kind, error)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_into_io_error() {
+        let error = std::io::Error::from(Error::Conversion(RawConversionError::NotANumber));
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert!(matches!(
+            error.get_ref().unwrap().downcast_ref::<Error>(),
+            Some(Error::Conversion(RawConversionError::NotANumber))
+        ));
+
+        let error = std::io::Error::from(Error::InvalidParameter("width"));
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+
+        let inner = std::io::Error::new(std::io::ErrorKind::BrokenPipe, "closed");
+        let error = std::io::Error::from(Error::from(inner));
+        assert_eq!(error.kind(), std::io::ErrorKind::BrokenPipe);
+        assert!(error.get_ref().unwrap().downcast_ref::<Error>().is_none());
+
+        let error = Error::from(std::io::Error::from(Error::NonFinite));
+        assert!(matches!(error, Error::NonFinite));
+    }
+}
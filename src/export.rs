@@ -0,0 +1,43 @@
+//! Serialization of the window contents back into the raw data, the counterpart of writing the raw data into the window.
+
+use crate::{ConverterToRaw, Error, RollingStats, WindowStorage};
+use alloc::vec::Vec;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    E: ConverterToRaw<T>,
+    S: WindowStorage<T>,
+{
+    /// Returns the items of the window, from the oldest to the newest one, serialized using the converter `E`.
+    /// Writing the returned raw data into an empty window of the same type recreates the window.
+    /// # Errors
+    /// Returns the `Error::Conversion` error if any of the items can't be serialized.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(E::encode_iter(self.buffer.iter().copied())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, LittleEndian, RollingStats};
+    use alloc::vec;
+
+    #[test]
+    fn round_trips() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new();
+        let _ = roller
+            .feed(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0])
+            .unwrap();
+        assert_eq!(
+            roller.to_bytes().unwrap(),
+            vec![0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4]
+        );
+
+        let mut roller = RollingStats::<f64, LittleEndian, 2>::new();
+        let _ = roller.feed(&1.5f64.to_le_bytes()).unwrap();
+        let mut copy = RollingStats::<f64, LittleEndian, 2>::new();
+        let _ = copy.feed(&roller.to_bytes().unwrap()).unwrap();
+        assert_eq!(copy.buffer, roller.buffer);
+    }
+}
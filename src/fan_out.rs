@@ -0,0 +1,80 @@
+//! FanOut forwards the same raw data stream to several writers, such as windows of different sizes or converters.
+
+use std::io::Write;
+
+/// A writer forwarding the raw data to all of the borrowed writers.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, FanOut, RollingStats, Statistics};
+/// use std::io::Write;
+///
+/// let mut short = RollingStats::<i32, BigEndian, 1>::new();
+/// let mut long = RollingStats::<i32, BigEndian, 2>::new();
+/// FanOut::new(vec![&mut short, &mut long])
+///     .write_all(&[0, 0, 0, 1, 0, 0, 0, 3])
+///     .unwrap();
+///
+/// assert_eq!(short.mean(), 3.0);
+/// assert_eq!(long.mean(), 2.0);
+/// ```
+pub struct FanOut<'a> {
+    writers: Vec<&'a mut dyn Write>,
+}
+
+impl<'a> FanOut<'a> {
+    /// Creates a new `FanOut` forwarding the raw data to the writers.
+    pub fn new(writers: Vec<&'a mut dyn Write>) -> Self {
+        Self { writers }
+    }
+
+    /// Adds another writer the raw data are forwarded to.
+    pub fn with(mut self, writer: &'a mut dyn Write) -> Self {
+        self.writers.push(writer);
+        self
+    }
+}
+
+impl Write for FanOut<'_> {
+    /// Writes the whole raw data to each of the writers in turn.
+    /// # Returns
+    /// Returns the length of the raw data, or the first error returned by any of the writers.
+    /// The writers following the failed one don't receive the raw data,
+    /// a writer consuming no data - such as a full window rejecting new values - fails with the `WriteZero` error.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for writer in self.writers.iter_mut() {
+            writer.write_all(buf)?;
+        }
+
+        Ok(buf.len())
+    }
+
+    /// Flushes all of the writers.
+    fn flush(&mut self) -> std::io::Result<()> {
+        for writer in self.writers.iter_mut() {
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BigEndian, LittleEndian, RollingStats};
+
+    #[test]
+    fn forwards_to_all() {
+        let mut big = RollingStats::<i32, BigEndian, 2>::new();
+        let mut little = RollingStats::<i32, LittleEndian, 2>::new();
+        let mut raw = Vec::new();
+
+        let mut fan_out = FanOut::new(vec![&mut big, &mut little]).with(&mut raw);
+        assert_eq!(fan_out.write(&[0, 0, 0, 1, 1]).unwrap(), 5);
+        fan_out.write_all(&[0, 0, 0]).unwrap();
+
+        assert_eq!(big.buffer, vec![1, 16777216]);
+        assert_eq!(little.buffer, vec![16777216, 1]);
+        assert_eq!(raw, vec![0, 0, 0, 1, 1, 0, 0, 0]);
+    }
+}
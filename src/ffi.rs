@@ -0,0 +1,215 @@
+//! A C interface of the window, so that it can be embedded into existing C and C++ software.
+//!
+//! The window is created with the type, the byte order and the size chosen at runtime and is accessed through an opaque pointer.
+//! The C declarations are in the `include/rolling_stats.h` header, the crate is built as a C library
+//! using `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+//!
+//! The functions are not expected to panic, but a panic must never unwind into the C code.
+//! A panic reaching an `extern "C"` function aborts the process since Rust 1.81, which the header documents,
+//! so the functions don't catch the panics themselves. The `rust-version` of the crate is 1.81 for that reason.
+
+use crate::{BigEndian, DynRollingStats, LittleEndian, Metric, NativeEndian, Statistics};
+use std::{boxed::Box, slice};
+
+/// The values are 32-bit signed integers.
+pub const ROLLING_STATS_I32: u32 = 0;
+/// The values are 32-bit floats.
+pub const ROLLING_STATS_F32: u32 = 1;
+/// The values are 64-bit floats.
+pub const ROLLING_STATS_F64: u32 = 2;
+
+/// The values are stored in the little endian byte order.
+pub const ROLLING_STATS_LITTLE_ENDIAN: u32 = 0;
+/// The values are stored in the big endian byte order.
+pub const ROLLING_STATS_BIG_ENDIAN: u32 = 1;
+/// The values are stored in the byte order of the target.
+pub const ROLLING_STATS_NATIVE_ENDIAN: u32 = 2;
+
+/// An opaque window computing the statistics in `double`.
+pub struct RollingStatsHandle(Box<dyn Window>);
+
+/// A window of any of the types, providing both the report and the individual statistics.
+trait Window: Metric<f64> + Statistics<f64> {}
+
+impl<W: Metric<f64> + Statistics<f64>> Window for W {}
+
+/// The statistics of the window, the C counterpart of the `Report`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct RollingStatsSummary {
+    /// The number of items in the window.
+    pub len: usize,
+    /// The mean of the window.
+    pub mean: f64,
+    /// The standard deviation of the window.
+    pub std_dev: f64,
+    /// The smallest item of the window, zero for an empty window.
+    pub min: f64,
+    /// The largest item of the window, zero for an empty window.
+    pub max: f64,
+}
+
+/// Creates a window of the values of the type parsed using the converter of the byte order.
+fn new_window<T>(endian: u32, window: usize) -> Option<Box<dyn Window>>
+where
+    DynRollingStats<T, LittleEndian, f64>: Window,
+    DynRollingStats<T, BigEndian, f64>: Window,
+    DynRollingStats<T, NativeEndian, f64>: Window,
+    T: 'static,
+{
+    Some(match endian {
+        ROLLING_STATS_LITTLE_ENDIAN => Box::new(
+            DynRollingStats::<T, LittleEndian, f64>::new_with_window(window),
+        ),
+        ROLLING_STATS_BIG_ENDIAN => Box::new(
+            DynRollingStats::<T, BigEndian, f64>::new_with_window(window),
+        ),
+        ROLLING_STATS_NATIVE_ENDIAN => Box::new(
+            DynRollingStats::<T, NativeEndian, f64>::new_with_window(window),
+        ),
+        _ => return None,
+    })
+}
+
+/// Creates a window of `window` values of the type `value_type` stored in the byte order `endian`.
+/// Returns a null pointer for an unknown type or byte order, the window is freed using `rolling_stats_free`.
+#[no_mangle]
+pub extern "C" fn rolling_stats_new(
+    value_type: u32,
+    endian: u32,
+    window: usize,
+) -> *mut RollingStatsHandle {
+    let metric = match value_type {
+        ROLLING_STATS_I32 => new_window::<i32>(endian, window),
+        ROLLING_STATS_F32 => new_window::<f32>(endian, window),
+        ROLLING_STATS_F64 => new_window::<f64>(endian, window),
+        _ => None,
+    };
+
+    metric.map_or(core::ptr::null_mut(), |metric| {
+        Box::into_raw(Box::new(RollingStatsHandle(metric)))
+    })
+}
+
+/// Frees the window.
+/// # Safety
+/// The pointer must be null or returned by `rolling_stats_new` and not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn rolling_stats_free(stats: *mut RollingStatsHandle) {
+    if !stats.is_null() {
+        drop(Box::from_raw(stats));
+    }
+}
+
+/// Parses the `len` bytes of raw data into the window, incomplete data are kept until the rest of the data is written.
+/// Returns the number of consumed bytes, or -1 if the window is null or the data can't be accepted,
+/// in which case the values parsed before the error are kept in the window.
+/// # Safety
+/// The window must be valid and `data` must point to `len` readable bytes, it may be null if `len` is zero.
+#[no_mangle]
+pub unsafe extern "C" fn rolling_stats_write(
+    stats: *mut RollingStatsHandle,
+    data: *const u8,
+    len: usize,
+) -> isize {
+    let stats = match stats.as_mut() {
+        Some(stats) => stats,
+        None => return -1,
+    };
+    let data = if len == 0 {
+        &[]
+    } else if data.is_null() {
+        return -1;
+    } else {
+        slice::from_raw_parts(data, len)
+    };
+
+    stats.0.feed(data).map_or(-1, |consumed| consumed as isize)
+}
+
+/// Returns the mean of the window, NaN for a null window.
+/// # Safety
+/// The window must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn rolling_stats_mean(stats: *const RollingStatsHandle) -> f64 {
+    stats.as_ref().map_or(f64::NAN, |stats| stats.0.mean())
+}
+
+/// Returns the standard deviation of the window, NaN for a null window.
+/// # Safety
+/// The window must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn rolling_stats_std_dev(stats: *const RollingStatsHandle) -> f64 {
+    stats.as_ref().map_or(f64::NAN, |stats| stats.0.std_dev())
+}
+
+/// Writes the statistics of the window into the summary.
+/// Returns zero on success, or -1 if any of the pointers is null.
+/// # Safety
+/// The window must be null or valid, the summary must be null or point to a writable `RollingStatsSummary`.
+#[no_mangle]
+pub unsafe extern "C" fn rolling_stats_summary(
+    stats: *const RollingStatsHandle,
+    summary: *mut RollingStatsSummary,
+) -> i32 {
+    let (stats, summary) = match (stats.as_ref(), summary.as_mut()) {
+        (Some(stats), Some(summary)) => (stats, summary),
+        _ => return -1,
+    };
+
+    let report = stats.0.report();
+    *summary = RollingStatsSummary {
+        len: report.len,
+        mean: report.mean,
+        std_dev: report.std_dev,
+        min: report.min,
+        max: report.max,
+    };
+    0
+}
+
+/// Empties the window, discarding the incomplete data.
+/// # Safety
+/// The window must be null or valid.
+#[no_mangle]
+pub unsafe extern "C" fn rolling_stats_clear(stats: *mut RollingStatsHandle) {
+    if let Some(stats) = stats.as_mut() {
+        stats.0.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn c_interface() {
+        unsafe {
+            assert!(rolling_stats_new(7, ROLLING_STATS_BIG_ENDIAN, 2).is_null());
+            assert!(rolling_stats_new(ROLLING_STATS_I32, 7, 2).is_null());
+
+            let stats = rolling_stats_new(ROLLING_STATS_I32, ROLLING_STATS_BIG_ENDIAN, 2);
+            assert!(!stats.is_null());
+            let data = [0, 0, 0, 1, 0, 0, 0, 3, 0, 0];
+            assert_eq!(rolling_stats_write(stats, data.as_ptr(), data.len()), 10);
+            assert_eq!(rolling_stats_write(stats, core::ptr::null(), 0), 0);
+            assert_eq!(rolling_stats_write(stats, core::ptr::null(), 1), -1);
+            assert_eq!(rolling_stats_mean(stats), 2.0);
+            assert_eq!(rolling_stats_std_dev(stats), 2f64.sqrt());
+
+            let mut summary = RollingStatsSummary::default();
+            assert_eq!(rolling_stats_summary(stats, &mut summary), 0);
+            assert_eq!((summary.len, summary.min, summary.max), (2, 1.0, 3.0));
+            assert_eq!(rolling_stats_summary(stats, core::ptr::null_mut()), -1);
+
+            rolling_stats_clear(stats);
+            assert_eq!(rolling_stats_write(stats, [0, 0, 0, 5].as_ptr(), 4), 4);
+            assert_eq!(rolling_stats_summary(stats, &mut summary), 0);
+            assert_eq!(summary.len, 1);
+            rolling_stats_free(stats);
+
+            assert!(rolling_stats_mean(core::ptr::null()).is_nan());
+            rolling_stats_free(core::ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Ingestion of large binary captures stored in files.
+
+use crate::{ConverterFromRaw, Error, RollingStats, WindowStorage};
+use std::{
+    fs::File,
+    io::{Read, Write},
+    path::Path,
+};
+
+/// The size of the chunks the files are read in.
+const CHUNK_SIZE: usize = 1 << 20;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    E: ConverterFromRaw<T>,
+    S: WindowStorage<T>,
+{
+    /// Writes the whole contents of the file into the window, reading the file in large chunks.
+    /// # Returns
+    /// Returns the number of bytes read.
+    /// # Errors
+    /// Returns the errors of reading the file and of the `write` method,
+    /// a window rejecting new values once full returns the `WriteZero` error.
+    pub fn ingest_file<P: AsRef<Path>>(&mut self, path: P) -> Result<u64, Error> {
+        self.ingest_file_with_progress(path, |_, _| {})
+    }
+
+    /// Writes the whole contents of the file into the window the same way the `ingest_file` method does,
+    /// calling the `progress` closure with the number of bytes read so far and the size of the file after each chunk.
+    pub fn ingest_file_with_progress<P, F>(
+        &mut self,
+        path: P,
+        mut progress: F,
+    ) -> Result<u64, Error>
+    where
+        P: AsRef<Path>,
+        F: FnMut(u64, u64),
+    {
+        let mut file = File::open(path)?;
+        let size = file.metadata()?.len();
+
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        let mut read_total = 0;
+        loop {
+            let read = match file.read(&mut buf) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            };
+            self.write_all(&buf[..read])?;
+
+            read_total += read as u64;
+            progress(read_total, size);
+        }
+
+        Ok(read_total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, Error, RollingStats, Statistics};
+
+    #[test]
+    fn ingests_file() {
+        let path = std::env::temp_dir().join(format!("rolling_stats_{}.bin", std::process::id()));
+        let data: Vec<u8> = (0..1_000_000i32).flat_map(|v| v.to_be_bytes()).collect();
+        std::fs::write(&path, &data).unwrap();
+
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new();
+        let mut reports = Vec::new();
+        let read = roller
+            .ingest_file_with_progress(&path, |read, size| reports.push((read, size)))
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(read, 4_000_000);
+        assert_eq!(reports.last(), Some(&(4_000_000, 4_000_000)));
+        assert!(reports.len() > 1);
+        assert_eq!(roller.mean(), 999_998.0);
+
+        assert!(matches!(roller.ingest_file(&path), Err(Error::Io(_))));
+    }
+}
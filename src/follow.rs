@@ -0,0 +1,259 @@
+//! Following of a growing file, such as a log or a capture appended to by another process, in the manner of `tail -f`.
+
+use crate::{ConverterFromRaw, Error, RollingStats, WindowStorage};
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The size of the chunks the followed file is read in.
+const CHUNK_SIZE: usize = 1 << 16;
+
+/// Follows a growing file, writing the newly appended bytes to a writer, such as the `RollingStats`.
+///
+/// Rotation of the file is detected - once the path refers to a new file, the rest of the old file is read and the new file is followed from its start.
+/// The same happens when the file is truncated. The rotation is detected using the file identity on unix platforms only, elsewhere only the truncation is detected.
+/// Incomplete data at the end of the old file are not discarded, so the files are expected to contain whole items.
+pub struct Follower {
+    path: PathBuf,
+    file: Option<File>,
+    position: u64,
+    identity: Option<(u64, u64)>,
+    from_start: bool,
+}
+
+impl Follower {
+    /// Creates a new `Follower` of the file, skipping the bytes present in the file when it is first polled.
+    /// The file doesn't have to exist yet, a file created after the first poll is read from its start.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            file: None,
+            position: 0,
+            identity: None,
+            from_start: false,
+        }
+    }
+
+    /// Creates a new `Follower` of the file, reading the file from its start.
+    pub fn from_start<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            from_start: true,
+            ..Self::new(path)
+        }
+    }
+
+    /// Returns the number of bytes of the currently followed file read so far, including the skipped ones.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Writes the bytes appended to the file since the last poll to the writer, handling the rotation and the truncation of the file.
+    /// # Returns
+    /// Returns the number of bytes written, zero if the file doesn't exist.
+    pub fn poll<W: Write>(&mut self, writer: &mut W) -> Result<u64, Error> {
+        if self.file.is_none() && !self.open(!self.from_start)? {
+            self.from_start = true;
+            return Ok(0);
+        }
+
+        let mut written = self.read_to(writer)?;
+        match std::fs::metadata(&self.path) {
+            Ok(metadata) if identity(&metadata) != self.identity => {
+                if self.open(false)? {
+                    written += self.read_to(writer)?;
+                }
+            }
+            Ok(metadata) if metadata.len() < self.position => {
+                if let Some(file) = self.file.as_mut() {
+                    file.seek(SeekFrom::Start(0))?;
+                }
+                self.position = 0;
+                written += self.read_to(writer)?;
+            }
+            Ok(_) => {}
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        Ok(written)
+    }
+
+    /// Polls the file repeatedly, sleeping for the `interval` whenever there are no new bytes.
+    /// After each poll, the `keep_running` closure is called with the number of bytes written, the following ends once it returns `false`.
+    pub fn run<W, F>(
+        &mut self,
+        writer: &mut W,
+        interval: Duration,
+        mut keep_running: F,
+    ) -> Result<(), Error>
+    where
+        W: Write,
+        F: FnMut(u64) -> bool,
+    {
+        loop {
+            let written = self.poll(writer)?;
+            if !keep_running(written) {
+                return Ok(());
+            }
+            if written == 0 {
+                std::thread::sleep(interval);
+            }
+        }
+    }
+
+    /// Opens the file, at its end or at its start.
+    /// # Returns
+    /// Returns `false` if the file doesn't exist.
+    fn open(&mut self, at_end: bool) -> Result<bool, Error> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+            Err(error) => return Err(error.into()),
+        };
+        let metadata = file.metadata()?;
+
+        self.position = if at_end { metadata.len() } else { 0 };
+        file.seek(SeekFrom::Start(self.position))?;
+        self.identity = identity(&metadata);
+        self.file = Some(file);
+        Ok(true)
+    }
+
+    /// Writes the bytes from the current position to the end of the file to the writer.
+    fn read_to<W: Write>(&mut self, writer: &mut W) -> Result<u64, Error> {
+        let file = match self.file.as_mut() {
+            Some(file) => file,
+            None => return Ok(0),
+        };
+
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        let mut written = 0;
+        loop {
+            let read = match file.read(&mut buf) {
+                Ok(0) => return Ok(written),
+                Ok(read) => read,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            };
+            writer.write_all(&buf[..read])?;
+            self.position += read as u64;
+            written += read as u64;
+        }
+    }
+}
+
+/// Returns the identity of the file - the device and the inode numbers.
+#[cfg(unix)]
+fn identity(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Returns the identity of the file, not available on this platform.
+#[cfg(not(unix))]
+fn identity(_: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    E: ConverterFromRaw<T>,
+    S: WindowStorage<T>,
+{
+    /// Follows the file the same way `tail -f` does, writing the newly appended bytes into the window, as set up by the `Follower::new` method.
+    /// The file is polled every `interval`, the `keep_running` closure is called after each poll with the window and the number of bytes written,
+    /// the following ends once it returns `false`.
+    pub fn follow<P, F>(
+        &mut self,
+        path: P,
+        interval: Duration,
+        mut keep_running: F,
+    ) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+        F: FnMut(&Self, u64) -> bool,
+    {
+        let mut follower = Follower::new(path);
+        loop {
+            let written = follower.poll(self)?;
+            if !keep_running(self, written) {
+                return Ok(());
+            }
+            if written == 0 {
+                std::thread::sleep(interval);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BigEndian;
+
+    fn append(path: &Path, data: &[u8]) {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        file.write_all(data).unwrap();
+    }
+
+    #[test]
+    fn follows_rotated_file() {
+        let path =
+            std::env::temp_dir().join(format!("rolling_stats_follow_{}.bin", std::process::id()));
+        let rotated = path.with_extension("old");
+        let _ = std::fs::remove_file(&path);
+
+        let mut roller = RollingStats::<i32, BigEndian, 4>::new();
+        let mut follower = Follower::new(&path);
+        assert_eq!(follower.poll(&mut roller).unwrap(), 0);
+
+        append(&path, &[0, 0, 0, 1]);
+        assert_eq!(follower.poll(&mut roller).unwrap(), 4);
+
+        append(&path, &[0, 0, 0, 2]);
+        std::fs::rename(&path, &rotated).unwrap();
+        append(&path, &[0, 0, 0, 3]);
+        assert_eq!(follower.poll(&mut roller).unwrap(), 8);
+        assert_eq!(roller.buffer, vec![1, 2, 3]);
+
+        std::fs::write(&path, []).unwrap();
+        assert_eq!(follower.poll(&mut roller).unwrap(), 0);
+        assert_eq!(follower.position(), 0);
+        append(&path, &[0, 0, 0, 4]);
+        assert_eq!(follower.poll(&mut roller).unwrap(), 4);
+        assert_eq!(roller.buffer, vec![1, 2, 3, 4]);
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&rotated).unwrap();
+    }
+
+    #[test]
+    fn skips_existing_bytes() {
+        let path =
+            std::env::temp_dir().join(format!("rolling_stats_tail_{}.bin", std::process::id()));
+        std::fs::write(&path, [0, 0, 0, 1]).unwrap();
+
+        let mut roller = RollingStats::<i32, BigEndian, 4>::new();
+        let mut polls = 0;
+        roller
+            .follow(&path, Duration::from_millis(1), |_, _| {
+                polls += 1;
+                if polls == 2 {
+                    append(&path, &[0, 0, 0, 2]);
+                }
+                polls < 3
+            })
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(roller.buffer, vec![2]);
+    }
+}
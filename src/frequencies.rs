@@ -0,0 +1,279 @@
+//! Categorical statistics of the window based on counting the occurrences of the values - mode, number of unique values and entropy.
+//!
+//! The counts can be either rebuilt on each query, or maintained incrementally as the values are pushed to and evicted from the window,
+//! which is enabled using the `RollingStats::with_mode_tracking` method.
+
+use crate::{RollingStats, WindowStorage};
+use alloc::{boxed::Box, collections::BTreeMap, vec, vec::Vec};
+use rand_distr::num_traits::Float;
+
+/// Counts of the values in the window, updated incrementally.
+///
+/// The values are grouped into buckets by their count, so that all of the updates, including finding
+/// a replacement of the mode evicted from the window, take logarithmic time in the number of distinct values.
+#[derive(Clone)]
+pub(crate) struct Frequencies<T> {
+    /// Number of occurrences of each value in the window and the position of the value in the bucket of its count.
+    counts: BTreeMap<T, (usize, usize)>,
+    /// The distinct values having a specific non-zero count, indexed by the count.
+    buckets: Vec<Vec<T>>,
+    /// The count of the most frequent value.
+    max_count: usize,
+    /// Total number of counted values.
+    total: usize,
+    /// Sum of `c * log2(c)` over all counts `c`, used for computing the entropy.
+    weighted_log_sum: f64,
+}
+
+impl<T> Default for Frequencies<T> {
+    fn default() -> Self {
+        Self {
+            counts: BTreeMap::new(),
+            buckets: vec![Vec::new()],
+            max_count: 0,
+            total: 0,
+            weighted_log_sum: 0.0,
+        }
+    }
+}
+
+impl<T: Copy + Ord> Frequencies<T> {
+    /// Moves the value from the bucket of the `from` count to the bucket of the `to` count, zero meaning no bucket.
+    fn relocate(&mut self, value: T, from: usize, to: usize) {
+        if from > 0 {
+            let index = self.counts[&value].1;
+            let bucket = &mut self.buckets[from];
+            bucket.swap_remove(index);
+            if let Some(moved) = bucket.get(index) {
+                if let Some(entry) = self.counts.get_mut(moved) {
+                    entry.1 = index;
+                }
+            }
+        }
+
+        if to > 0 {
+            if self.buckets.len() <= to {
+                self.buckets.push(Vec::new());
+            }
+            self.buckets[to].push(value);
+            let index = self.buckets[to].len() - 1;
+            if let Some(entry) = self.counts.get_mut(&value) {
+                *entry = (to, index);
+            }
+        } else {
+            self.counts.remove(&value);
+        }
+    }
+}
+
+/// Returns `c * log2(c)`, zero for zero count.
+fn weighted_log(count: usize) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        count as f64 * Float::log2(count as f64)
+    }
+}
+
+/// Type erased access to the frequencies, so that the ingestion of data doesn't require `T` to be ordered.
+pub(crate) trait FrequencyTracker<T>: Send {
+    /// Counts a value pushed to the window.
+    fn insert(&mut self, value: T);
+
+    /// Discounts a value evicted from the window.
+    fn remove(&mut self, value: T);
+
+    /// Discards all of the counts.
+    fn clear(&mut self);
+
+    /// Returns one of the most frequent values.
+    fn mode(&self) -> Option<T>;
+
+    /// Returns the count of the most frequent value.
+    fn max_count(&self) -> usize;
+
+    /// Returns the number of distinct values.
+    fn unique_count(&self) -> usize;
+
+    /// Returns the Shannon entropy of the values in bits.
+    fn entropy(&self) -> f64;
+
+    /// Returns a copy of the counts.
+    fn clone_box(&self) -> Box<dyn FrequencyTracker<T>>;
+}
+
+impl<T> FrequencyTracker<T> for Frequencies<T>
+where
+    T: Copy + Ord + Send + 'static,
+{
+    fn insert(&mut self, value: T) {
+        let count = self.counts.entry(value).or_insert((0, 0)).0 + 1;
+        self.relocate(value, count - 1, count);
+
+        self.weighted_log_sum += weighted_log(count) - weighted_log(count - 1);
+        self.total += 1;
+        self.max_count = self.max_count.max(count);
+    }
+
+    fn remove(&mut self, value: T) {
+        let count = match self.counts.get(&value) {
+            Some((count, _)) => *count,
+            None => return,
+        };
+        self.relocate(value, count, count - 1);
+
+        self.weighted_log_sum += weighted_log(count - 1) - weighted_log(count);
+        self.total -= 1;
+
+        if count == self.max_count && self.buckets[count].is_empty() {
+            // the value was the only one with the maximal count, so it remains the mode with the decreased count
+            self.max_count -= 1;
+        }
+    }
+
+    fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    fn mode(&self) -> Option<T> {
+        self.buckets[self.max_count].last().copied()
+    }
+
+    fn max_count(&self) -> usize {
+        self.max_count
+    }
+
+    fn unique_count(&self) -> usize {
+        self.counts.len()
+    }
+
+    fn clone_box(&self) -> Box<dyn FrequencyTracker<T>> {
+        Box::new(self.clone())
+    }
+
+    fn entropy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        let total = self.total as f64;
+        (Float::log2(total) - self.weighted_log_sum / total).max(0.0)
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Ord + Send + 'static,
+    S: WindowStorage<T>,
+{
+    /// Enables incremental tracking of the value counts as the values are pushed to and evicted from the window.
+    /// The `mode`, `unique_count`, `entropy` and `mode_ratio` queries then don't need to rebuild the counts.
+    /// Each push and eviction updates the counts in logarithmic time in the number of distinct values.
+    pub fn with_mode_tracking(mut self) -> Self {
+        let mut frequencies = Frequencies::default();
+        for value in self.buffer.iter() {
+            frequencies.insert(*value);
+        }
+        self.frequencies = Some(Box::new(frequencies));
+        self
+    }
+
+    /// Calls `f` with the counts of the window values, either the incrementally tracked ones or counts rebuilt from the window.
+    fn with_frequencies<R>(&self, f: impl FnOnce(&dyn FrequencyTracker<T>) -> R) -> R {
+        match self.frequencies.as_deref() {
+            Some(frequencies) => f(frequencies),
+            None => {
+                let mut frequencies = Frequencies::default();
+                for value in self.buffer.iter() {
+                    frequencies.insert(*value);
+                }
+                f(&frequencies)
+            }
+        }
+    }
+
+    /// Returns the most frequent value in the window, in the case of a tie any of the most frequent values,
+    /// or `None` for an empty window.
+    pub fn mode(&self) -> Option<T> {
+        self.with_frequencies(|frequencies| frequencies.mode())
+    }
+
+    /// Returns the number of distinct values in the window.
+    pub fn unique_count(&self) -> usize {
+        self.with_frequencies(|frequencies| frequencies.unique_count())
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Ord + Send + 'static,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns the Shannon entropy of the values in the window in bits, zero for an empty window.
+    pub fn entropy(&self) -> O {
+        O::from(self.with_frequencies(|frequencies| frequencies.entropy())).unwrap()
+    }
+
+    /// Returns the share of the window occupied by the most frequent value, zero for an empty window.
+    pub fn mode_ratio(&self) -> O {
+        if self.is_empty() {
+            return O::zero();
+        }
+
+        let max_count = self.with_frequencies(|frequencies| frequencies.max_count());
+        O::from(max_count).unwrap() / O::from(self.len()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LittleEndian, RollingStats};
+    use approx::*;
+    #[cfg(feature = "std")]
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn categorical_statistics() {
+        let mut roller = RollingStats::<i32, LittleEndian, 4>::new().with_mode_tracking();
+        for value in [1i32, 2, 2, 3] {
+            let _ = roller.feed(&value.to_le_bytes()).unwrap();
+        }
+
+        assert_eq!(roller.mode(), Some(2));
+        assert_eq!(roller.unique_count(), 3);
+        assert_abs_diff_eq!(roller.entropy(), 1.5);
+        assert_abs_diff_eq!(roller.mode_ratio(), 0.5);
+
+        let _ = roller.feed(&3i32.to_le_bytes()).unwrap();
+        let _ = roller.feed(&3i32.to_le_bytes()).unwrap();
+
+        assert_eq!(roller.mode(), Some(3));
+        assert_eq!(roller.unique_count(), 2);
+        assert_abs_diff_eq!(roller.mode_ratio(), 0.75);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn incremental_counts_match_rebuilt_counts() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut tracked = RollingStats::<i32, LittleEndian, 50>::new().with_mode_tracking();
+        let mut rebuilt = RollingStats::<i32, LittleEndian, 50>::new();
+
+        for _ in 0..10_000 {
+            let value: i32 = rng.gen_range(0..8);
+            let _ = tracked.feed(&value.to_le_bytes()).unwrap();
+            let _ = rebuilt.feed(&value.to_le_bytes()).unwrap();
+
+            let mode = tracked.mode().unwrap();
+            let mode_count = tracked.buffer.iter().filter(|v| **v == mode).count();
+            assert_eq!(tracked.mode_ratio(), rebuilt.mode_ratio());
+            assert_abs_diff_eq!(
+                mode_count as f32 / tracked.len() as f32,
+                rebuilt.mode_ratio()
+            );
+            assert_eq!(tracked.unique_count(), rebuilt.unique_count());
+            assert_abs_diff_eq!(tracked.entropy(), rebuilt.entropy(), epsilon = 1e-4);
+        }
+    }
+}
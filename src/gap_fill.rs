@@ -0,0 +1,118 @@
+//! Gap filling replaces the items marked as missing by a sentinel value, keeping the positions of the items in the window.
+//! This matters for evenly sampled data, where the position of an item encodes time, so the missing items can't be just dropped.
+
+use crate::{convertfloat::LossyFloatConvertible, NativeEndian, RollingStats, WindowStorage};
+use alloc::vec::Vec;
+use rand_distr::num_traits::Float;
+
+/// Denotes how the items marked as missing by a sentinel value are treated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GapFill {
+    /// The sentinels are kept in the window as they are.
+    #[default]
+    Keep,
+    /// The sentinels are replaced by a linear interpolation of the nearest non-sentinel items on both sides.
+    /// Sentinels at the edges of the window, having a non-sentinel item on one side only, are replaced by the nearest non-sentinel item.
+    Interpolate,
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Sets the sentinel value marking missing items and the way the missing items are filled by the `filled` method.
+    pub fn with_gap_fill(mut self, sentinel: T, fill: GapFill) -> Self {
+        self.gap_fill = Some((sentinel, fill));
+        self
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + PartialEq + LossyFloatConvertible<O>,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns a float window holding the items of this window, with the missing items filled as set using the `with_gap_fill` method.
+    /// All of the statistics can then be computed on the filled window.
+    ///
+    /// A window consisting only of sentinels is returned as is, as there are no items to fill the gaps from.
+    pub fn filled(&self) -> RollingStats<O, NativeEndian, WINDOW_SIZE, O> {
+        let mut values: Vec<O> = self.buffer.iter().map(|item| item.convert()).collect();
+
+        if let Some((sentinel, GapFill::Interpolate)) = self.gap_fill {
+            let known: Vec<usize> = self
+                .buffer
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| **item != sentinel)
+                .map(|(index, _)| index)
+                .collect();
+
+            let mut next = 0;
+            for index in 0..values.len() {
+                if next < known.len() && known[next] == index {
+                    next += 1;
+                    continue;
+                }
+
+                let previous = next.checked_sub(1).map(|i| known[i]);
+                values[index] = match (previous, known.get(next)) {
+                    (Some(previous), Some(next)) => {
+                        let fraction =
+                            O::from(index - previous).unwrap() / O::from(next - previous).unwrap();
+                        values[previous] + (values[*next] - values[previous]) * fraction
+                    }
+                    (Some(previous), None) => values[previous],
+                    (None, Some(next)) => values[*next],
+                    (None, None) => values[index],
+                };
+            }
+        }
+
+        let mut window = RollingStats::new();
+        window.window_size = self.window_size;
+        window.buffer = values.into();
+        window
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{LittleEndian, Statistics};
+    use approx::*;
+    use std::io::Write;
+
+    fn roller(data: &[i32]) -> RollingStats<i32, LittleEndian, 8> {
+        let mut roller = RollingStats::default();
+        for value in data {
+            let _ = roller.write(&value.to_le_bytes()).unwrap();
+        }
+        roller
+    }
+
+    #[test]
+    fn interpolate() {
+        let roller = roller(&[-1, 2, -1, -1, 8, -1]).with_gap_fill(-1, GapFill::Interpolate);
+        let filled = roller.filled();
+
+        assert_eq!(filled.buffer, vec![2.0, 2.0, 4.0, 6.0, 8.0, 8.0]);
+        assert_abs_diff_eq!(filled.mean(), 5.0);
+    }
+
+    #[test]
+    fn keep() {
+        let roller = roller(&[-1, 2, 3]);
+        assert_eq!(roller.filled().buffer, vec![-1.0, 2.0, 3.0]);
+
+        let roller = roller.with_gap_fill(-1, GapFill::Keep);
+        assert_eq!(roller.filled().buffer, vec![-1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn only_sentinels() {
+        let roller = roller(&[-1, -1]).with_gap_fill(-1, GapFill::Interpolate);
+        assert_eq!(roller.filled().buffer, vec![-1.0, -1.0]);
+    }
+}
@@ -0,0 +1,78 @@
+//! Ingestion of the raw data reporting what happened to the data, as opposed to the plain `std::io::Write` interface and the `feed` method.
+
+use crate::{ConverterFromRaw, Error, RollingStats, WindowStorage};
+
+/// A summary of a single ingestion of raw data, returned by the `ingest` method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IngestReport {
+    /// The number of raw bytes consumed, less than the length of the raw data only if the window rejects values once full.
+    pub consumed: usize,
+    /// The number of values parsed from the raw data, including the skipped ones.
+    pub parsed: usize,
+    /// The number of parsed values that never entered the window.
+    pub skipped: usize,
+    /// The number of values evicted from the window to make room for the new ones.
+    pub evicted: usize,
+    /// The number of values detected as anomalous, zero unless the detection is enabled using the `with_anomaly_detection` method.
+    pub anomalies: usize,
+    /// The number of raw bytes of incomplete data waiting for the rest of the data after the ingestion.
+    pub pending_bytes: usize,
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    E: ConverterFromRaw<T>,
+    S: WindowStorage<T>,
+{
+    /// Writes the raw data the same way the `feed` method does, returning a summary of the ingestion.
+    /// # Errors
+    /// Returns the same errors as the `feed` method, the values parsed before the error are kept in the window.
+    pub fn ingest(&mut self, buf: &[u8]) -> Result<IngestReport, Error> {
+        let (total, skipped, evicted) = (self.total, self.skipped, self.evicted);
+        let anomalies = self.anomaly_count();
+
+        let consumed = self.feed(buf)?;
+
+        Ok(IngestReport {
+            consumed,
+            parsed: self.total - total,
+            skipped: self.skipped - skipped,
+            evicted: self.evicted - evicted,
+            anomalies: self.anomaly_count() - anomalies,
+            pending_bytes: self.pending_bytes(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, Error, NonFinitePolicy, RollingStats};
+
+    #[test]
+    fn reports_ingestion() {
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new().with_sentinels([0]);
+
+        let report = roller.ingest(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(report.consumed, 10);
+        assert_eq!(report.parsed, 2);
+        assert_eq!(report.skipped, 1);
+        assert_eq!(report.evicted, 0);
+        assert_eq!(report.pending_bytes, 2);
+
+        let report = roller.ingest(&[0, 2, 0, 0, 0, 3]).unwrap();
+        assert_eq!(report.parsed, 2);
+        assert_eq!(report.skipped, 0);
+        assert_eq!(report.evicted, 1);
+        assert_eq!(report.pending_bytes, 0);
+    }
+
+    #[test]
+    fn returns_crate_error() {
+        let mut roller =
+            RollingStats::<f32, BigEndian, 2>::new().with_non_finite_policy(NonFinitePolicy::Error);
+
+        let error = roller.ingest(&f32::NAN.to_be_bytes()).unwrap_err();
+        assert!(matches!(error, Error::NonFinite));
+    }
+}
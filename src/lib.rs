@@ -3,6 +3,7 @@
 //!
 //! # Basic use
 //! ```
+//! # #[cfg(feature = "std")] {
 //! use rolling_stats::{LittleEndian, RollingStats, Statistics};
 //! use std::io::Write;
 //! use approx::*;
@@ -12,107 +13,722 @@
 //!     .write(&[1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0])
 //!     .unwrap();
 //! assert_abs_diff_eq!(roller.mean(), 3.0);
+//! # }
 //! ```
+//!
+//! # no_std
+//! Disabling the default `std` feature makes the crate usable on `no_std` targets with an allocator.
+//! The raw data are then written using the `feed` method instead of the `std::io::Write` trait,
+//! the features depending on the operating system, such as the ingestion from files or sockets, are not available.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
-mod convertf32;
+extern crate alloc;
+
+mod alarm;
+mod anomaly;
+#[cfg(feature = "audio")]
+mod audio;
+mod bivariate;
+mod builder;
+mod convertfloat;
+mod decay;
+mod dynamic;
+mod error;
+mod export;
+#[cfg(feature = "std")]
+mod fan_out;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+mod file;
+#[cfg(feature = "std")]
+mod follow;
+mod frequencies;
+mod gap_fill;
+mod ingest;
+mod merge;
+mod multi_resolution;
+#[cfg(feature = "net")]
+mod net;
+mod observer;
+mod order;
+#[cfg(feature = "std")]
+mod parallel;
 mod partial_data_buffer;
+mod policy;
+#[cfg(feature = "prometheus")]
+mod prometheus;
 mod raw;
 mod reconstructor;
+mod registry;
+mod report;
+mod sampler;
+#[cfg(feature = "std")]
+mod shared;
+mod signal;
+mod smoothing;
+mod split;
+mod statistics_ext;
+mod storage;
+#[cfg(feature = "std")]
+mod tee;
+#[cfg(feature = "std")]
+mod text;
+mod tumbling;
+#[cfg(feature = "std")]
+mod worker;
 
-use core::marker::PhantomData;
-use std::{collections::VecDeque, io::Write, ops::Add};
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc, vec::Vec};
+use core::{
+    fmt::{Debug, Formatter},
+    iter::FromIterator,
+    marker::PhantomData,
+    ops::Add,
+};
+#[cfg(feature = "std")]
+use std::io::{IoSlice, Write};
 
-#[cfg(not(feature = "reconstructor"))]
 use crate::partial_data_buffer::PartialDataBuffer;
-use convertf32::LossyF32Convertible;
-use rand_distr::{Distribution, Normal};
-pub use raw::{BigEndian, ConverterFromRaw, LittleEndian};
-#[cfg(feature = "reconstructor")]
-use reconstructor::Reconstructor;
+pub use alarm::{AlarmSource, AlarmState, ThresholdAlarm};
+pub use anomaly::Anomaly;
+use anomaly::AnomalyDetector;
+#[cfg(feature = "audio")]
+pub use audio::{WavFormat, WavHeader, WavReader};
+pub use builder::RollingStatsBuilder;
+use convertfloat::LossyFloatConvertible;
+pub use dynamic::DynRollingStats;
+pub use error::Error;
+#[cfg(feature = "std")]
+pub use fan_out::FanOut;
+#[cfg(feature = "std")]
+pub use follow::Follower;
+use frequencies::FrequencyTracker;
+pub use gap_fill::GapFill;
+pub use ingest::IngestReport;
+pub use multi_resolution::MultiResolutionStats;
+#[cfg(feature = "net")]
+pub use net::{TcpIngest, UdpIngest};
+pub use observer::Observer;
+pub use policy::{EvictionPolicy, NonFinitePolicy, ReconstructionStrategy};
+use rand_distr::{
+    num_traits::{Bounded, Float},
+    Distribution, StandardNormal,
+};
+pub use raw::{
+    BigEndian, CanonicalNan, ConverterFromRaw, ConverterToRaw, LittleEndian, NativeEndian,
+    RawConversionError, RejectNan, RuntimeWidth,
+};
+pub use reconstructor::Reconstructor;
+pub use registry::{Metric, Registry};
+use report::Trajectory;
+pub use report::{Report, StatsDelta};
+pub use sampler::GaussianSampler;
+#[cfg(feature = "std")]
+pub use sampler::ThreadRngSampler;
+#[cfg(feature = "std")]
+pub use shared::SharedRollingStats;
+pub use split::{Reader, Writer};
+pub use statistics_ext::StatisticsExt;
+pub use storage::{ArrayDeque, Drain, WindowStorage};
+#[cfg(feature = "std")]
+pub use tee::Tee;
+#[cfg(feature = "std")]
+pub use text::{TextDecoder, TextEncoding};
+use tumbling::Tumbling;
+#[cfg(feature = "std")]
+pub use worker::{StatsHandle, Worker};
 
 /// The `Statistics` trait useful for dependency injection.
 /// This trait abstracts away basic statistics measures.
-pub trait Statistics {
+/// `O` denotes the float type the statistics are computed in.
+pub trait Statistics<O = f32> {
     /// Returns the mean of a dataset.
-    fn mean(&self) -> f32;
+    fn mean(&self) -> O;
 
     /// Returns standard deviation of a dataset.
-    fn std_dev(&self) -> f32;
+    fn std_dev(&self) -> O;
 
     /// Returns a number from a standard distribution specified by the mean and standard deviation of the dataset.
-    fn rand(&self) -> f32;
+    /// Sampled using the `ThreadRngSampler` by default, so that the implementors don't depend on the `std` feature.
+    #[cfg(feature = "std")]
+    fn rand(&self) -> O
+    where
+        O: Float,
+        StandardNormal: Distribution<O>,
+    {
+        ThreadRngSampler.sample(self.mean(), self.std_dev())
+    }
+}
+
+/// A sink the values evicted from the window are forwarded to.
+type EvictionSink<T> = Box<dyn FnMut(T) -> Result<(), Error> + Send>;
+
+/// A transformation applied to the parsed values before they are pushed to the window.
+type Transform<T> = Arc<dyn Fn(T) -> T + Send + Sync>;
+
+/// A predicate deciding whether a parsed value is accepted into the window.
+type Filter<T> = Arc<dyn Fn(&T) -> bool + Send + Sync>;
+
+/// The reconstruction of the incomplete raw data according to the `ReconstructionStrategy`.
+enum Decoder<T, E> {
+    PartialDataBuffer(PartialDataBuffer<T, E>),
+    Reconstructor(Reconstructor<T, E>),
+}
+
+impl<T, E> Decoder<T, E> {
+    /// Creates the decoder of items `type_size` bytes long.
+    fn new(strategy: ReconstructionStrategy, type_size: usize) -> Self {
+        match strategy {
+            ReconstructionStrategy::PartialDataBuffer => {
+                Self::PartialDataBuffer(PartialDataBuffer::with_type_size(type_size))
+            }
+            ReconstructionStrategy::Reconstructor => {
+                Self::Reconstructor(Reconstructor::with_type_size(type_size))
+            }
+        }
+    }
+
+    fn strategy(&self) -> ReconstructionStrategy {
+        match self {
+            Self::PartialDataBuffer(_) => ReconstructionStrategy::PartialDataBuffer,
+            Self::Reconstructor(_) => ReconstructionStrategy::Reconstructor,
+        }
+    }
 }
 
 /// Rolling stats is an implementation of a rolling buffer specified by a fixed size window, providing significant statistical values.
 ///
-/// The raw data are written to the `RollingStats` using the `std::io::Write` trait.
-/// As for handling partially received data, there are two choices, that can be enabled using a feature. The default one is more performant.
+/// The raw data are written to the `RollingStats` using the `std::io::Write` trait, or the `feed` method.
+/// As for handling partially received data, there are two strategies, selected using the `with_reconstruction_strategy` method.
+/// The default one is more performant, unless the `reconstructor` feature selects the other one.
 ///
 /// # Type parameters
 /// * `T` - the type to be reconstructed from raw data.
 /// * `E` - denotes a way to convert the raw data into the specified type
-pub struct RollingStats<T, E, const WINDOW_SIZE: usize> {
+/// * `O` - the float type the statistics are computed in, `f32` by default
+/// * `S` - the storage of the window items, a `VecDeque` by default,
+///   an `ArrayDeque` of `WINDOW_SIZE` items stores the items inline, without allocating them
+pub struct RollingStats<T, E, const WINDOW_SIZE: usize, O = f32, S = VecDeque<T>> {
     _e: PhantomData<E>,
-    #[cfg(feature = "reconstructor")]
-    reconstructor: Reconstructor<T, E>,
-    #[cfg(not(feature = "reconstructor"))]
-    intermediate_buffer: PartialDataBuffer<T, E>,
-    buffer: VecDeque<T>,
+    _o: PhantomData<O>,
+    decoder: Decoder<T, E>,
+    buffer: S,
+    window_size: usize,
+    ddof: usize,
+    decay: Option<O>,
+    sink: Option<EvictionSink<T>>,
+    observers: Vec<Box<dyn Observer<T> + Send>>,
+    frequencies: Option<Box<dyn FrequencyTracker<T>>>,
+    trajectory: Option<Trajectory<Self, O>>,
+    tumbling: Option<Tumbling<T, O>>,
+    anomaly: Option<AnomalyDetector<Self, T, O>>,
+    gap_fill: Option<(T, GapFill)>,
+    alarm: AlarmState,
+    transform: Option<Transform<T>>,
+    filter: Option<Filter<T>>,
+    sentinels: Option<Filter<T>>,
+    non_finite: Option<(NonFinitePolicy, Filter<T>)>,
+    skipped: usize,
+    clip: Option<Filter<T>>,
+    clipped: usize,
+    evicted: usize,
+    total: usize,
+    strict_alignment: Option<usize>,
+    partial_writes: usize,
+    eviction_policy: EvictionPolicy,
 }
 
-impl<T, E, const WINDOW_SIZE: usize> RollingStats<T, E, WINDOW_SIZE> {
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
     /// Returns the number of items currently stored in the `RollingStats` struct.
-    /// The maximal value returned is `WINDOW_SIZE`.
+    /// The maximal value returned is the window size.
     pub fn len(&self) -> usize {
         self.buffer.len()
     }
+
+    /// Returns `true` if the window holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Returns `true` if the window holds as many items as its capacity, so that the statistics are computed over a fully populated window.
+    pub fn is_full(&self) -> bool {
+        self.buffer.len() >= self.window_size
+    }
+
+    /// Returns the maximal number of items held by the window, the same as the `window_size` method.
+    pub fn capacity(&self) -> usize {
+        self.window_size
+    }
+
+    /// Limits the window size to the capacity of a fixed capacity storage.
+    pub(crate) fn fitting_window_size(&self, window_size: usize) -> usize {
+        self.buffer
+            .fixed_capacity()
+            .map_or(window_size, |capacity| window_size.min(capacity))
+    }
+
+    /// Returns an iterator over the items of the window in the order they were pushed, the oldest first.
+    pub fn iter(&self) -> S::Iter<'_> {
+        self.buffer.iter()
+    }
+
+    /// Returns the newest item of the window, or `None` for an empty window.
+    pub fn latest(&self) -> Option<&T> {
+        self.buffer.iter().next_back()
+    }
+
+    /// Returns the oldest item of the window - the next one to be evicted, or `None` for an empty window.
+    pub fn oldest(&self) -> Option<&T> {
+        self.buffer.iter().next()
+    }
+
+    /// Returns the items of the window as two slices, the oldest items first, without copying.
+    /// The second slice is non-empty only if the items wrap around the end of the storage, see the `make_contiguous` method.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        self.buffer.as_slices()
+    }
+
+    /// Rearranges the items of the window so that they are stored in a single slice, which can be passed to the routines expecting a slice.
+    /// # Returns
+    /// Returns the items of the window, the oldest first.
+    pub fn make_contiguous(&mut self) -> &[T] {
+        self.buffer.make_contiguous()
+    }
+
+    /// Returns the maximal number of items held by the window, `WINDOW_SIZE` unless the window is sized at runtime.
+    pub fn window_size(&self) -> usize {
+        self.window_size
+    }
+
+    /// Returns the number of parsed values that were skipped by the filter, as sentinels or as non-finite values and never entered the window.
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Returns the number of accepted values that were clipped - reached the bounds set using the `with_clip_range` or `with_clip_detection` method.
+    /// Saturated readings usually indicate gain problems of the source.
+    pub fn clipped_count(&self) -> usize {
+        self.clipped
+    }
+
+    /// Returns the number of values evicted from the window since its creation.
+    /// Comparing it to the number of values read between queries tells whether the statistics are queried often enough.
+    pub fn evicted(&self) -> usize {
+        self.evicted
+    }
+
+    /// Returns the number of values ever parsed from the raw data, including the skipped ones, regardless of the window size.
+    /// Zero means no data were received yet, as opposed to an empty window.
+    pub fn total_count(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the number of raw bytes of incomplete data waiting for the rest of the data.
+    /// A non-zero value after writing a complete frame indicates a misaligned stream.
+    pub fn pending_bytes(&self) -> usize {
+        match &self.decoder {
+            Decoder::PartialDataBuffer(buffer) => buffer.pending_bytes(),
+            Decoder::Reconstructor(reconstructor) => reconstructor.pending_bytes(),
+        }
+    }
+
+    /// Returns the size of the raw representation of the items.
+    fn type_size(&self) -> usize {
+        match &self.decoder {
+            Decoder::PartialDataBuffer(buffer) => buffer.type_size(),
+            Decoder::Reconstructor(reconstructor) => reconstructor.type_size(),
+        }
+    }
+
+    /// Empties the window, discards the partial data, the recorded trajectory, the tumbling windows and the anomalies, resets the counters and the alarm state.
+    /// The configuration of the window is kept, so the window behaves as if it was just created.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        if let Some(frequencies) = self.frequencies.as_mut() {
+            frequencies.clear();
+        }
+        if let Some(trajectory) = self.trajectory.as_mut() {
+            trajectory.clear();
+        }
+        if let Some(tumbling) = self.tumbling.as_mut() {
+            tumbling.clear();
+        }
+        if let Some(detector) = self.anomaly.as_mut() {
+            detector.clear();
+        }
+        self.discard_pending();
+        self.alarm = AlarmState::default();
+        self.skipped = 0;
+        self.clipped = 0;
+        self.evicted = 0;
+        self.total = 0;
+        self.partial_writes = 0;
+    }
+
+    /// Returns an iterator removing the items of the window, the oldest first, so that the window can be handed over without copying.
+    /// The window is left empty once the iterator is dropped, its configuration, counters and partial data are kept.
+    /// The drained items are not counted as evicted and are not forwarded to the eviction sink.
+    pub fn drain(&mut self) -> Drain<'_, T, S> {
+        if let Some(frequencies) = self.frequencies.as_mut() {
+            frequencies.clear();
+        }
+        Drain::new(&mut self.buffer)
+    }
+
+    /// Discards the partial data waiting for the rest of the data.
+    fn discard_pending(&mut self) {
+        match &mut self.decoder {
+            Decoder::PartialDataBuffer(buffer) => buffer.clear(),
+            Decoder::Reconstructor(reconstructor) => reconstructor.discard_pending(),
+        }
+    }
+
+    /// Checks the alignment of the data after a write in the strict alignment mode.
+    /// Once the partial data are left over more consecutive writes than allowed, the partial data are discarded and an error is returned.
+    fn check_alignment(&mut self) -> Result<(), Error> {
+        let max_partial_writes = match self.strict_alignment {
+            Some(max_partial_writes) => max_partial_writes,
+            None => return Ok(()),
+        };
+
+        if self.pending_bytes() == 0 {
+            self.partial_writes = 0;
+            return Ok(());
+        }
+
+        self.partial_writes += 1;
+        if self.partial_writes > max_partial_writes {
+            self.partial_writes = 0;
+            let discarded = self.pending_bytes();
+            self.discard_pending();
+            self.notify(|observer| observer.on_resync(discarded));
+
+            return Err(Error::Misaligned);
+        }
+
+        Ok(())
+    }
+
+    /// Pushes an already decoded value to the window the same way the values parsed from the raw data are treated:
+    /// non-finite values are treated according to the policy, sentinels and values rejected by the filter are skipped,
+    /// otherwise the transformation is applied before the value enters the window, evicting the oldest value.
+    /// # Errors
+    /// Returns the `Error::WindowFull` error if the window is full and the `EvictionPolicy::RejectWhenFull` policy is set,
+    /// the `Error::NonFinite` error for a non-finite value with the `NonFinitePolicy::Error` policy, or the error of the eviction sink.
+    pub fn push(&mut self, value: T) -> Result<(), Error>
+    where
+        T: Copy,
+    {
+        if self.eviction_policy == EvictionPolicy::RejectWhenFull && self.len() >= self.window_size
+        {
+            return Err(Error::WindowFull);
+        }
+
+        match self.admit(value)? {
+            Some(value) => self.insert(value),
+            None => Ok(()),
+        }
+    }
+
+    /// Pushes the already decoded values the same way as the `push` method does,
+    /// except that the storage is reserved and the window is evicted once for the whole slice.
+    /// The values of the slice that would be evicted by the following values of the slice never enter the storage,
+    /// they are counted as evicted and forwarded to the sink right away.
+    /// # Errors
+    /// Returns the same errors as the `push` method, the values admitted before the error are kept in the window.
+    /// If the eviction sink fails, the values of the slice that were neither forwarded nor inserted are not counted by the `total_count`.
+    pub fn extend_from_slice(&mut self, values: &[T]) -> Result<(), Error>
+    where
+        T: Copy,
+    {
+        // the trajectory records the window after each value, the anomalies are detected against the preceding values,
+        // the rejection applies to each value, the observers observe each value before the following values evict it
+        if self.trajectory.is_some()
+            || self.anomaly.is_some()
+            || !self.observers.is_empty()
+            || self.eviction_policy == EvictionPolicy::RejectWhenFull
+        {
+            return values.iter().try_for_each(|value| self.push(*value));
+        }
+
+        let mut admitted = Vec::with_capacity(values.len());
+        let mut result = Ok(());
+        for value in values {
+            match self.admit(*value) {
+                Ok(Some(value)) => admitted.push(value),
+                Ok(None) => {}
+                Err(error) => {
+                    result = Err(error);
+                    break;
+                }
+            }
+        }
+
+        let kept = admitted.len().min(self.window_size);
+        if let Err(error) = self.evict(self.window_size - kept) {
+            self.total -= admitted.len();
+            return Err(error);
+        }
+        for (index, value) in admitted[..admitted.len() - kept].iter().enumerate() {
+            if let Err(error) = self.forward(*value) {
+                // the failed value is counted as evicted, the following ones never enter the window
+                self.total -= admitted.len() - index - 1;
+                return Err(error);
+            }
+        }
+
+        self.buffer.reserve(kept);
+        for value in &admitted[admitted.len() - kept..] {
+            if let Some(frequencies) = self.frequencies.as_mut() {
+                frequencies.insert(*value);
+            }
+            self.buffer.push_back(*value);
+        }
+        for value in &admitted {
+            self.record_tumbling(*value);
+        }
+
+        result
+    }
+
+    /// Treats non-finite values according to the policy, skips sentinels and values rejected by the filter and applies the transformation.
+    /// # Returns
+    /// Returns the value to be inserted into the window, or `None` if the value was skipped.
+    fn admit(&mut self, value: T) -> Result<Option<T>, Error>
+    where
+        T: Copy,
+    {
+        self.total += 1;
+
+        if let Some((policy, is_finite)) = self.non_finite.as_ref() {
+            if !is_finite(&value) {
+                match policy {
+                    NonFinitePolicy::Propagate => {}
+                    NonFinitePolicy::Skip => {
+                        self.skipped += 1;
+                        return Ok(None);
+                    }
+                    NonFinitePolicy::Error => return Err(Error::NonFinite),
+                }
+            }
+        }
+
+        let rejected = [self.sentinels.as_ref(), self.filter.as_ref()]
+            .iter()
+            .flatten()
+            .any(|filter| !filter(&value));
+        if rejected {
+            self.skipped += 1;
+            return Ok(None);
+        }
+
+        if self
+            .clip
+            .as_ref()
+            .is_some_and(|is_clipped| is_clipped(&value))
+        {
+            self.clipped += 1;
+        }
+
+        Ok(Some(match self.transform.as_ref() {
+            Some(transform) => transform(value),
+            None => value,
+        }))
+    }
+
+    /// Inserts a new item to the window, evicting the oldest item to make room for it and recording the trajectory and the tumbling windows if enabled.
+    fn insert(&mut self, value: T) -> Result<(), Error>
+    where
+        T: Copy,
+    {
+        if self.window_size == 0 {
+            // the value doesn't fit into the window at all
+            self.discard(value)?;
+        } else {
+            self.detect_anomaly(value);
+            let full = self.buffer.len() >= self.window_size;
+            self.evict(self.window_size - 1)?;
+            if let Some(frequencies) = self.frequencies.as_mut() {
+                frequencies.insert(value);
+            }
+            self.buffer.push_back(value);
+            self.notify(|observer| observer.on_sample(&value));
+            if !full && self.buffer.len() == self.window_size {
+                self.notify(|observer| observer.on_full());
+            }
+        }
+        self.record_trajectory();
+        self.record_tumbling(value);
+
+        Ok(())
+    }
+
+    /// Removes the oldest items until at most `len` items remain in the window.
+    fn evict(&mut self, len: usize) -> Result<(), Error>
+    where
+        T: Copy,
+    {
+        while self.buffer.len() > len {
+            match self.buffer.pop_front() {
+                Some(value) => self.discard(value)?,
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Discounts an item leaving the window and forwards it.
+    fn discard(&mut self, value: T) -> Result<(), Error>
+    where
+        T: Copy,
+    {
+        if let Some(frequencies) = self.frequencies.as_mut() {
+            frequencies.remove(value);
+        }
+        self.forward(value)
+    }
+
+    /// Counts an item as evicted and forwards it to the sink if there is one.
+    fn forward(&mut self, value: T) -> Result<(), Error>
+    where
+        T: Copy,
+    {
+        self.evicted += 1;
+        self.notify(|observer| observer.on_evict(&value));
+        if let Some(sink) = self.sink.as_mut() {
+            sink(value)?;
+        }
+
+        Ok(())
+    }
 }
 
-#[cfg(feature = "reconstructor")]
-impl<T, E, const WINDOW_SIZE: usize> Write for RollingStats<T, E, WINDOW_SIZE>
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
 where
     T: Copy,
     E: ConverterFromRaw<T>,
+    S: WindowStorage<T>,
 {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let result = self.reconstructor.write(buf);
-
-        self.buffer.extend(self.reconstructor.data());
-        self.reconstructor.flush()?;
-        while self.buffer.len() > WINDOW_SIZE {
-            self.buffer.pop_front();
+    /// Parses the raw data using the reconstruction strategy and accepts the parsed values, notifying the observers of the conversion errors.
+    fn write_raw(&mut self, buf: &[u8]) -> Result<(), Error> {
+        let result = self.decode_raw(buf);
+        if let Err(Error::Conversion(error)) = &result {
+            self.notify(|observer| observer.on_conversion_error(error));
         }
 
         result
     }
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        self.reconstructor.flush()
+    /// Parses the raw data using the reconstruction strategy and accepts the parsed values.
+    fn decode_raw(&mut self, buf: &[u8]) -> Result<(), Error> {
+        let type_size = self.type_size();
+        let (reconstructed, remaining_buf) = match &mut self.decoder {
+            Decoder::PartialDataBuffer(buffer) => buffer.consume(buf),
+            Decoder::Reconstructor(reconstructor) => {
+                let result = reconstructor.feed(buf);
+                for value in reconstructor.take_data() {
+                    self.push(value)?;
+                }
+
+                result?;
+                return Ok(());
+            }
+        };
+
+        if let Some(data) = reconstructed {
+            self.push(data?)?;
+        }
+
+        for raw in remaining_buf.chunks_exact(type_size) {
+            self.push(E::from_raw(raw)?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the raw data according to the eviction policy.
+    /// # Returns
+    /// Returns the number of bytes consumed.
+    fn write_slice(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        match self.eviction_policy {
+            EvictionPolicy::Evict => {
+                self.write_raw(buf)?;
+                Ok(buf.len())
+            }
+            EvictionPolicy::RejectWhenFull => self.write_until_full(buf),
+        }
+    }
+
+    /// Writes the raw data value by value until the window is full.
+    /// # Returns
+    /// Returns the number of bytes consumed before the window became full.
+    fn write_until_full(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let mut consumed = 0;
+        while consumed < buf.len() && self.len() < self.window_size {
+            let missing = self.type_size() - self.pending_bytes();
+            let end = buf.len().min(consumed + missing);
+            self.write_raw(&buf[consumed..end])?;
+            consumed = end;
+        }
+
+        Ok(consumed)
+    }
+
+    /// Parses the raw data and pushes the parsed values to the window, evicting the oldest values.
+    /// The counterpart of the `std::io::Write::write` method, available without the `std` feature.
+    /// # Returns
+    /// Returns the number of consumed bytes - the length of the input raw data,
+    /// unless the `EvictionPolicy::RejectWhenFull` policy is set, in which case the bytes following the value that filled the window are not consumed.
+    ///
+    /// Returns an error when the data can't be accepted, the values parsed before the error are kept in the window.
+    pub fn feed(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let written = self.write_slice(buf)?;
+        self.check_alignment()?;
+
+        Ok(written)
     }
 }
 
-#[cfg(not(feature = "reconstructor"))]
-impl<T, E, const WINDOW_SIZE: usize> Write for RollingStats<T, E, WINDOW_SIZE>
+/// The trait `std::io::Write` represents the raw data input into the `RollingStats`.
+/// Trailing bytes of incomplete data are stored until the rest of the data is written, so they count as consumed by the write.
+#[cfg(feature = "std")]
+impl<T, E, const WINDOW_SIZE: usize, O, S> Write for RollingStats<T, E, WINDOW_SIZE, O, S>
 where
     T: Copy,
     E: ConverterFromRaw<T>,
+    S: WindowStorage<T>,
 {
+    /// Parses the raw data and pushes the parsed values to the window, evicting the oldest values.
+    /// # Returns
+    /// Returns the number of consumed bytes - the length of the input raw data,
+    /// unless the `EvictionPolicy::RejectWhenFull` policy is set, in which case the bytes following the value that filled the window are not consumed.
+    /// Once the window is full, zero is returned, which makes `write_all` and `std::io::copy` fail with the `WriteZero` error.
+    ///
+    /// Returns an error when the data can't be accepted, the values parsed before the error are kept in the window.
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let (reconstructed, remaining_buf) = self.intermediate_buffer.consume(&buf);
-        if let Some(data) = reconstructed {
-            self.buffer.push_back(data);
-        }
-
-        let parsed = remaining_buf
-            .chunks_exact(std::mem::size_of::<T>())
-            .map(|raw| E::from_raw(raw).unwrap());
+        Ok(self.feed(buf)?)
+    }
 
-        self.buffer.extend(parsed);
-        while self.buffer.len() > WINDOW_SIZE {
-            self.buffer.pop_front();
+    /// Parses the raw data of all of the slices as if they were written at once, incomplete data may span the slice boundaries.
+    /// # Returns
+    /// Returns the number of consumed bytes the same way the `write` method does, the slices following a partially consumed slice are not consumed.
+    fn write_vectored(&mut self, bufs: &[IoSlice<'_>]) -> std::io::Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            let consumed = self.write_slice(buf)?;
+            written += consumed;
+            if consumed < buf.len() {
+                break;
+            }
         }
+        self.check_alignment()?;
 
-        Ok(buf.len())
+        Ok(written)
     }
 
     fn flush(&mut self) -> std::io::Result<()> {
@@ -120,58 +736,395 @@ where
     }
 }
 
-impl<T, E, const WINDOW_SIZE: usize> RollingStats<T, E, WINDOW_SIZE> {
-    /// Creates a new instance of the `RollingStats` with empty buffer.
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Creates a new instance of the `RollingStats` with empty buffer, holding at most `WINDOW_SIZE` items.
+    ///
+    /// The size of a `DynRollingStats` created this way is zero, so it discards every value until resized,
+    /// use the `new_with_window` constructor instead.
     pub fn new() -> Self {
+        let mut stats = Self {
+            _e: PhantomData,
+            _o: PhantomData,
+            decoder: Decoder::new(ReconstructionStrategy::default(), core::mem::size_of::<T>()),
+            buffer: Default::default(),
+            window_size: 0,
+            ddof: 1,
+            decay: None,
+            sink: None,
+            observers: Vec::new(),
+            frequencies: None,
+            trajectory: None,
+            tumbling: None,
+            anomaly: None,
+            gap_fill: None,
+            alarm: AlarmState::default(),
+            transform: None,
+            filter: None,
+            sentinels: None,
+            non_finite: None,
+            skipped: 0,
+            clip: None,
+            clipped: 0,
+            evicted: 0,
+            total: 0,
+            strict_alignment: None,
+            partial_writes: 0,
+            eviction_policy: EvictionPolicy::default(),
+        };
+        stats.window_size = stats.fitting_window_size(WINDOW_SIZE);
+        stats
+    }
+
+    /// Sets a transformation applied to every parsed value before it is pushed to the window,
+    /// such as a unit conversion, scaling or taking the absolute value.
+    pub fn with_transform<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        self.transform = Some(Arc::new(transform));
+        self
+    }
+
+    /// Sets a predicate deciding whether a parsed value enters the window, the rejected values are counted as skipped.
+    /// The predicate is evaluated on the parsed values, before the transformation is applied.
+    pub fn with_filter<F>(mut self, filter: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        self.filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// Sets the sentinel values, such as `0xFFFF` or `-32768`, encoding a missing reading.
+    /// The sentinels are excluded from the window and counted as skipped.
+    pub fn with_sentinels<I>(mut self, sentinels: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: PartialEq + Send + Sync + 'static,
+    {
+        let sentinels: Vec<T> = sentinels.into_iter().collect();
+        self.sentinels = Some(Arc::new(move |value| !sentinels.contains(value)));
+        self
+    }
+
+    /// Sets how the non-finite (NaN or infinite) parsed values are treated, so that they don't poison the statistics.
+    /// The values are propagated into the window by default.
+    pub fn with_non_finite_policy(mut self, policy: NonFinitePolicy) -> Self
+    where
+        T: Float,
+    {
+        self.non_finite = Some((policy, Arc::new(|value: &T| value.is_finite())));
+        self
+    }
+
+    /// Enables counting of the clipped values - values equal to or beyond the bounds of the range.
+    /// The values are checked before the transformation is applied, the clipped values still enter the window.
+    pub fn with_clip_range(mut self, min: T, max: T) -> Self
+    where
+        T: PartialOrd + Send + Sync + 'static,
+    {
+        self.clip = Some(Arc::new(move |value| *value <= min || *value >= max));
+        self
+    }
+
+    /// Enables counting of the clipped values - values equal to the minimal or maximal value of the type.
+    pub fn with_clip_detection(self) -> Self
+    where
+        T: PartialOrd + Bounded + Send + Sync + 'static,
+    {
+        self.with_clip_range(T::min_value(), T::max_value())
+    }
+
+    /// Enables the strict alignment mode for links guaranteeing frame-aligned writes, where misaligned data mean corruption.
+    /// When partial data are left over more than `max_partial_writes` consecutive writes, the write returns an `InvalidData` error
+    /// and the partial data are discarded. With `max_partial_writes` set to zero, any partial data are an error.
+    pub fn with_strict_alignment(mut self, max_partial_writes: usize) -> Self {
+        self.strict_alignment = Some(max_partial_writes);
+        self
+    }
+
+    /// Sets the policy applied once the window is full.
+    /// With the `EvictionPolicy::RejectWhenFull` policy the full window doesn't accept any more data until it is cleared using the `clear` method,
+    /// which allows computing statistics over exactly the first values filling the window.
+    pub fn with_eviction_policy(mut self, policy: EvictionPolicy) -> Self {
+        self.eviction_policy = policy;
+        self
+    }
+
+    /// Sets the strategy of reconstructing the incomplete raw data, the one selected by the `reconstructor` feature by default.
+    /// Any incomplete data pending in the window are discarded.
+    pub fn with_reconstruction_strategy(mut self, strategy: ReconstructionStrategy) -> Self {
+        self.decoder = Decoder::new(strategy, self.type_size());
+        self
+    }
+
+    /// Sets the delta degrees of freedom of the standard deviation - the divisor is the number of items minus `ddof`, at least one.
+    /// The default of one computes the sample standard deviation, zero computes the population standard deviation.
+    pub fn with_ddof(mut self, ddof: usize) -> Self {
+        self.ddof = ddof;
+        self
+    }
+
+    /// Sets a sink the values evicted from the window are written to, serialized using the converter `E`.
+    /// The `RollingStats` then acts as a fixed delay line, emitting the old values while computing statistics on the recent ones.
+    ///
+    /// Errors returned by the sink are surfaced from the `write` method.
+    #[cfg(feature = "std")]
+    pub fn with_sink<W>(mut self, mut sink: W) -> Self
+    where
+        W: Write + Send + 'static,
+        E: ConverterToRaw<T>,
+    {
+        self.sink = Some(Box::new(move |value| {
+            sink.write_all(&E::encode(value)?)?;
+            Ok(())
+        }));
+        self
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: LossyFloatConvertible<f32>,
+    S: WindowStorage<T>,
+{
+    /// Returns a new float window holding the items of this window converted into `f32`, in the same order.
+    /// Allows for writing code generic over the type of the items, dealing only with `f32` windows.
+    pub fn to_f32_window(&self) -> RollingStats<f32, NativeEndian, WINDOW_SIZE> {
+        let mut window = RollingStats::new();
+        window.window_size = self.window_size;
+        window.buffer = self.buffer.iter().map(|item| item.convert()).collect();
+        window
+    }
+}
+
+impl<E, const WINDOW_SIZE: usize, O, S> RollingStats<i64, RuntimeWidth<E>, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<i64>,
+{
+    /// Creates a new instance of the `RollingStats` with empty buffer, decoding signed integers `width` bytes long.
+    /// Meant for streams whose item width is known only at runtime, all of the items are stored as `i64`.
+    ///
+    /// # Panics
+    /// Panics if the width is not in the range from 1 to 8 bytes.
+    pub fn with_width(width: usize) -> Self {
+        Self::try_with_width(width).expect("The width has to be 1 to 8 bytes.")
+    }
+
+    /// Creates a new instance of the `RollingStats` with empty buffer, decoding signed integers `width` bytes long.
+    /// # Errors
+    /// Returns the `Error::InvalidParameter` error if the width is not in the range from 1 to 8 bytes.
+    pub fn try_with_width(width: usize) -> Result<Self, Error> {
+        if !(1..=core::mem::size_of::<i64>()).contains(&width) {
+            return Err(Error::InvalidParameter("The width has to be 1 to 8 bytes."));
+        }
+
+        let mut stats = Self::new();
+        stats.decoder = Decoder::new(stats.decoder.strategy(), width);
+        Ok(stats)
+    }
+}
+
+/// Creates a snapshot of the window, including its configuration and counters.
+/// The eviction sink is not cloned, the values evicted from the clone are dropped.
+impl<T, E, const WINDOW_SIZE: usize, O, S> Clone for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Clone,
+    O: Clone,
+    S: WindowStorage<T> + Clone,
+{
+    fn clone(&self) -> Self {
         Self {
             _e: PhantomData,
-            #[cfg(not(feature = "reconstructor"))]
-            intermediate_buffer: Default::default(),
-            #[cfg(feature = "reconstructor")]
-            reconstructor: Reconstructor::default(),
-            buffer: VecDeque::<T>::new(),
+            _o: PhantomData,
+            decoder: match &self.decoder {
+                Decoder::PartialDataBuffer(buffer) => Decoder::PartialDataBuffer(buffer.clone()),
+                Decoder::Reconstructor(reconstructor) => {
+                    Decoder::Reconstructor(reconstructor.clone())
+                }
+            },
+            buffer: self.buffer.clone(),
+            window_size: self.window_size,
+            ddof: self.ddof,
+            decay: self.decay.clone(),
+            sink: None,
+            observers: Vec::new(),
+            frequencies: self
+                .frequencies
+                .as_ref()
+                .map(|frequencies| frequencies.clone_box()),
+            trajectory: self.trajectory.clone(),
+            tumbling: self.tumbling.clone(),
+            anomaly: self.anomaly.clone(),
+            gap_fill: self.gap_fill.clone(),
+            alarm: self.alarm,
+            transform: self.transform.clone(),
+            filter: self.filter.clone(),
+            sentinels: self.sentinels.clone(),
+            non_finite: self.non_finite.clone(),
+            skipped: self.skipped,
+            clip: self.clip.clone(),
+            clipped: self.clipped,
+            evicted: self.evicted,
+            total: self.total,
+            strict_alignment: self.strict_alignment,
+            partial_writes: self.partial_writes,
+            eviction_policy: self.eviction_policy,
+        }
+    }
+}
+
+/// The number of items of the window shown by the `Debug` implementation.
+const DEBUG_PREVIEW_LEN: usize = 8;
+
+/// Formats the size and the counters of the window, and a preview of up to eight of the newest items.
+impl<T, E, const WINDOW_SIZE: usize, O, S> Debug for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Debug,
+    S: WindowStorage<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        /// The newest items of the window, preceded by the number of the omitted older items.
+        struct Preview<'a, T, S>(&'a S, PhantomData<T>);
+
+        impl<T: Debug, S: WindowStorage<T>> Debug for Preview<'_, T, S> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                let omitted = self.0.len().saturating_sub(DEBUG_PREVIEW_LEN);
+                let mut list = f.debug_list();
+                if omitted > 0 {
+                    list.entry(&format_args!("...{} more", omitted));
+                }
+                list.entries(self.0.iter().skip(omitted)).finish()
+            }
         }
+
+        f.debug_struct("RollingStats")
+            .field("window_size", &self.window_size)
+            .field("len", &self.len())
+            .field("items", &Preview(&self.buffer, PhantomData))
+            .field("pending_bytes", &self.pending_bytes())
+            .field("total", &self.total)
+            .field("skipped", &self.skipped)
+            .field("evicted", &self.evicted)
+            .finish()
     }
 }
 
-impl<T, E, const WINDOW_SIZE: usize> Default for RollingStats<T, E, WINDOW_SIZE> {
+/// Two windows are equal if they have the same size and hold equal items in the same order,
+/// the configuration, the counters and the partial data are not compared.
+impl<T, E, const WINDOW_SIZE: usize, O, S> PartialEq for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: PartialEq,
+    S: WindowStorage<T>,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.window_size == other.window_size
+            && self.buffer.len() == other.buffer.len()
+            && self.buffer.iter().eq(other.buffer.iter())
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> Default for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T, E, const WINDOW_SIZE: usize> Statistics for RollingStats<T, E, WINDOW_SIZE>
+/// Pushes the values using the `push` method, evicting the oldest values as usual.
+/// The values failing to be pushed, such as when the window is full with the `EvictionPolicy::RejectWhenFull` policy, are dropped,
+/// the `push` method has to be used to handle the errors.
+impl<T, E, const WINDOW_SIZE: usize, O, S> Extend<T> for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    S: WindowStorage<T>,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            let _ = self.push(value);
+        }
+    }
+}
+
+/// Creates a window holding the last values of the iterator.
+/// A `DynRollingStats` is sized to hold all of the values, up to the capacity of a fixed capacity storage.
+impl<T, E, const WINDOW_SIZE: usize, O, S> FromIterator<T> for RollingStats<T, E, WINDOW_SIZE, O, S>
 where
-    T: Copy + Default + Add<T, Output = T> + LossyF32Convertible,
+    T: Copy,
+    S: WindowStorage<T>,
 {
-    fn mean(&self) -> f32 {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut stats = Self::new();
+        if WINDOW_SIZE == 0 {
+            let values: Vec<T> = iter.into_iter().collect();
+            stats.window_size = stats.fitting_window_size(values.len());
+            stats.extend(values);
+        } else {
+            stats.extend(iter);
+        }
+        stats
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns the mean of the window ignoring the time decay, zero for an empty window.
+    pub(crate) fn arithmetic_mean(&self) -> O {
         self.buffer
             .iter()
             .fold(T::default(), |acc, item| acc + *item)
             .convert()
-            / WINDOW_SIZE.min(self.buffer.len()).max(1) as f32
+            / O::from(self.window_size.min(self.buffer.len()).max(1)).unwrap()
     }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> Statistics<O> for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    fn mean(&self) -> O {
+        if let Some(decay) = self.decay {
+            return self.decayed_mean(decay);
+        }
+
+        self.arithmetic_mean()
+    }
+
+    fn std_dev(&self) -> O {
+        if let Some(decay) = self.decay {
+            return self.decayed_std_dev(decay);
+        }
 
-    fn std_dev(&self) -> f32 {
         let mean = self.mean();
 
         let sum = self
             .buffer
             .iter()
-            .fold(0.0, |acc, item| acc + (item.convert() - mean).powi(2));
+            .fold(O::zero(), |acc, item| acc + (item.convert() - mean).powi(2));
 
-        let divisor = WINDOW_SIZE.min(self.buffer.len()).max(2) - 1;
+        let divisor = self
+            .window_size
+            .min(self.buffer.len())
+            .saturating_sub(self.ddof)
+            .max(1);
 
-        (sum / divisor as f32).sqrt()
-    }
-
-    fn rand(&self) -> f32 {
-        let dist = Normal::new(self.mean(), self.std_dev()).unwrap();
-        dist.sample(&mut rand::thread_rng())
+        (sum / O::from(divisor).unwrap()).sqrt()
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use approx::*;
@@ -179,19 +1132,8 @@ mod tests {
 
     #[test]
     fn test_basic_functionality() {
-        let mut buffer = VecDeque::new();
-        buffer.push_back(5);
-        buffer.push_back(5);
-        buffer.push_back(5);
-
-        let roller = RollingStats::<i32, LittleEndian, 3> {
-            _e: PhantomData,
-            #[cfg(not(feature = "reconstructor"))]
-            intermediate_buffer: PartialDataBuffer::default(),
-            #[cfg(feature = "reconstructor")]
-            reconstructor: Reconstructor::default(),
-            buffer,
-        };
+        let mut roller = RollingStats::<i32, LittleEndian, 3>::new();
+        roller.buffer = [5, 5, 5].iter().copied().collect();
 
         assert_abs_diff_eq!(roller.mean(), 5.0);
     }
@@ -202,6 +1144,7 @@ mod tests {
         let _ = roller.write(&[0, 0, 0]);
 
         assert_eq!(roller.len(), 0);
+        assert_eq!(roller.pending_bytes(), 3);
         assert_abs_diff_eq!(roller.mean(), 0.0);
 
         let _ = roller.write(&[1]);
@@ -212,6 +1155,7 @@ mod tests {
         let _ = roller.write(&[0, 0, 0, 2, 0]);
 
         assert_eq!(roller.len(), 2);
+        assert_eq!(roller.pending_bytes(), 1);
         assert_abs_diff_eq!(roller.mean(), 1.5);
 
         let _ = roller.write(&[0, 0, 3]);
@@ -237,4 +1181,477 @@ mod tests {
             .unwrap();
         assert_abs_diff_eq!(roller.std_dev(), 1.0);
     }
+
+    #[test]
+    fn reconstruction_strategies() {
+        let mut buffered = RollingStats::<i32, BigEndian, 3>::new()
+            .with_reconstruction_strategy(ReconstructionStrategy::PartialDataBuffer);
+        let mut reconstructed = RollingStats::<i32, BigEndian, 3>::new()
+            .with_reconstruction_strategy(ReconstructionStrategy::Reconstructor);
+        for roller in [&mut buffered, &mut reconstructed] {
+            roller.write_all(&[0, 0, 0, 1, 0, 0]).unwrap();
+            assert_eq!(roller.pending_bytes(), 2);
+            roller.write_all(&[0, 2, 0, 0, 0, 3, 0]).unwrap();
+        }
+
+        assert_eq!(buffered.buffer, vec![1, 2, 3]);
+        assert_eq!(reconstructed.buffer, buffered.buffer);
+        assert_eq!(reconstructed.pending_bytes(), 1);
+    }
+
+    #[test]
+    fn push_values() {
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new()
+            .with_filter(|value| *value > 0)
+            .with_eviction_policy(EvictionPolicy::RejectWhenFull);
+        roller.push(1).unwrap();
+        roller.push(-1).unwrap();
+        roller.write_all(&2i32.to_be_bytes()).unwrap();
+
+        assert_eq!(roller.buffer, vec![1, 2]);
+        assert_eq!(roller.skipped(), 1);
+        assert!(matches!(roller.push(3), Err(Error::WindowFull)));
+        assert_eq!(roller.total_count(), 3);
+    }
+
+    #[test]
+    fn collect_and_extend() {
+        let mut roller: RollingStats<i32, BigEndian, 3> = (1..=4).collect();
+        assert_eq!(roller.buffer, vec![2, 3, 4]);
+
+        roller.extend(vec![5, 6]);
+        assert_eq!(roller.buffer, vec![4, 5, 6]);
+        assert_eq!(roller.evicted(), 3);
+        assert_eq!(roller.mean(), 5.0);
+    }
+
+    #[test]
+    fn extend_from_slice() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new()
+            .with_filter(|value| *value != 0)
+            .with_mode_tracking();
+        roller.extend_from_slice(&[1, 2]).unwrap();
+        roller.extend_from_slice(&[3, 0, 4, 5, 6]).unwrap();
+
+        assert_eq!(roller.buffer, vec![4, 5, 6]);
+        assert_eq!(roller.evicted(), 3);
+        assert_eq!(roller.skipped(), 1);
+        assert_eq!(roller.unique_count(), 3);
+        assert_eq!(roller.total_count(), 7);
+
+        let mut pushed = RollingStats::<i32, BigEndian, 3>::new().with_trajectory(2);
+        pushed.extend_from_slice(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(pushed.buffer, vec![2, 3, 4]);
+        assert_eq!(pushed.trajectory().len(), 2);
+    }
+
+    #[test]
+    fn extend_from_slice_sink_error() {
+        // the sink accepts a single value
+        let sink = std::io::Cursor::new([0u8; 4]);
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new().with_sink(sink);
+        roller.extend_from_slice(&[1, 2]).unwrap();
+        assert!(roller.extend_from_slice(&[3, 4, 5]).is_err());
+        assert_eq!(roller.total_count(), 2);
+        assert_eq!(roller.evicted(), 2);
+        assert!(roller.is_empty());
+
+        let sink = std::io::Cursor::new([0u8; 4]);
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new().with_sink(sink);
+        assert!(roller.extend_from_slice(&[1, 2, 3, 4]).is_err());
+        assert_eq!(roller.total_count(), 2);
+        assert_eq!(roller.evicted(), 2);
+        assert!(roller.is_empty());
+    }
+
+    #[test]
+    fn iterates_window() {
+        let roller: RollingStats<i32, BigEndian, 3> = (1..=5).collect();
+        assert_eq!(roller.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(roller.iter().next_back(), Some(&5));
+    }
+
+    #[test]
+    fn fullness() {
+        let mut roller = DynRollingStats::<i32, BigEndian>::new_with_window(2);
+        assert!(roller.is_empty());
+        assert!(!roller.is_full());
+        assert_eq!(roller.capacity(), 2);
+
+        roller.extend([1, 2]);
+        assert!(!roller.is_empty());
+        assert!(roller.is_full());
+    }
+
+    #[test]
+    fn boundary_items() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new();
+        assert_eq!(roller.latest(), None);
+        assert_eq!(roller.oldest(), None);
+
+        roller.extend(1..=4);
+        assert_eq!(roller.latest(), Some(&4));
+        assert_eq!(roller.oldest(), Some(&2));
+    }
+
+    #[test]
+    fn slices() {
+        let mut roller: RollingStats<i32, BigEndian, 3> = (1..=3).collect();
+        roller.push(4).unwrap();
+        let (first, second) = roller.as_slices();
+        assert_eq!([first, second].concat(), vec![2, 3, 4]);
+        assert_eq!(roller.make_contiguous(), &[2, 3, 4]);
+        assert_eq!(roller.as_slices().0, &[2, 3, 4]);
+    }
+
+    #[test]
+    fn population_std_dev() {
+        let mut roller = RollingStats::<i32, LittleEndian, 4>::new().with_ddof(0);
+        let _ = roller
+            .write(&[2, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0, 0, 6, 0, 0, 0])
+            .unwrap();
+        assert_abs_diff_eq!(roller.std_dev(), 2.0f32.sqrt());
+    }
+
+    #[test]
+    fn test_transform() {
+        let mut roller =
+            RollingStats::<i32, BigEndian, 3>::new().with_transform(|v: i32| v.abs() * 10);
+        let _ = roller.write(&(-1i32).to_be_bytes()).unwrap();
+        let _ = roller.write(&[0, 0, 0, 2, 0, 0]).unwrap();
+        let _ = roller.write(&[0, 3]).unwrap();
+
+        assert_eq!(roller.buffer, vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new()
+            .with_filter(|v: &i32| *v >= 0)
+            .with_transform(|v: i32| v * 2);
+        for value in [1i32, -1, 2, -2, -3] {
+            let _ = roller.write(&value.to_be_bytes()).unwrap();
+        }
+
+        assert_eq!(roller.buffer, vec![2, 4]);
+        assert_eq!(roller.skipped(), 3);
+    }
+
+    #[test]
+    fn test_sentinels() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new()
+            .with_sentinels([-32768, 0xffff])
+            .with_filter(|v: &i32| *v < 100);
+        for value in [1i32, -32768, 2, 0xffff, 100] {
+            let _ = roller.write(&value.to_be_bytes()).unwrap();
+        }
+
+        assert_eq!(roller.buffer, vec![1, 2]);
+        assert_eq!(roller.skipped(), 3);
+    }
+
+    #[test]
+    fn test_non_finite_policy() {
+        let data = [1.0f32, f32::NAN, 3.0, f32::INFINITY];
+
+        let mut roller = RollingStats::<f32, LittleEndian, 4>::new();
+        for value in data {
+            let _ = roller.write(&value.to_le_bytes()).unwrap();
+        }
+        assert!(roller.mean().is_nan());
+
+        let mut roller = RollingStats::<f32, LittleEndian, 4>::new()
+            .with_non_finite_policy(NonFinitePolicy::Skip);
+        for value in data {
+            let _ = roller.write(&value.to_le_bytes()).unwrap();
+        }
+        assert_abs_diff_eq!(roller.mean(), 2.0);
+        assert_eq!(roller.skipped(), 2);
+
+        let mut roller = RollingStats::<f32, LittleEndian, 4>::new()
+            .with_non_finite_policy(NonFinitePolicy::Error);
+        let error = roller.write(&f32::NAN.to_le_bytes()).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(roller.len(), 0);
+    }
+
+    #[test]
+    fn test_clipped_count() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new().with_clip_detection();
+        for value in [1, i32::MAX, 2, i32::MIN] {
+            let _ = roller.write(&value.to_be_bytes()).unwrap();
+        }
+        assert_eq!(roller.clipped_count(), 2);
+        assert_eq!(roller.len(), 3);
+
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new().with_clip_range(0, 1023);
+        for value in [0i32, 512, 1023, 2000] {
+            let _ = roller.write(&value.to_be_bytes()).unwrap();
+        }
+        assert_eq!(roller.clipped_count(), 3);
+    }
+
+    #[test]
+    fn test_evicted() {
+        let mut roller = RollingStats::<i32, BigEndian, 2>::default();
+        let _ = roller.write(&[0, 0, 0, 1, 0, 0, 0, 2]).unwrap();
+        assert_eq!(roller.evicted(), 0);
+
+        let _ = roller.write(&[0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0, 5]).unwrap();
+        assert_eq!(roller.evicted(), 3);
+    }
+
+    #[test]
+    fn test_total_count() {
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new().with_sentinels([0]);
+        let _ = roller.write(&[0, 0, 0, 0, 0, 0]).unwrap();
+        assert_eq!(roller.total_count(), 1);
+        assert_eq!(roller.len(), 0);
+
+        let _ = roller.write(&[0, 1, 0, 0, 0, 2, 0, 0, 0, 3]).unwrap();
+        assert_eq!(roller.total_count(), 4);
+        assert_eq!(roller.len(), 2);
+    }
+
+    #[test]
+    fn test_strict_alignment() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new().with_strict_alignment(0);
+        assert!(roller.write(&[0, 0, 0, 1]).is_ok());
+
+        let error = roller.write(&[0, 0, 0, 2, 0]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(roller.len(), 2);
+        assert_eq!(roller.pending_bytes(), 0);
+
+        let mut roller = RollingStats::<i32, BigEndian, 3>::new().with_strict_alignment(1);
+        assert!(roller.write(&[0, 0]).is_ok());
+        assert!(roller.write(&[0, 1]).is_ok());
+        assert!(roller.write(&[0, 0, 0, 2, 0]).is_ok());
+        assert!(roller.write(&[0]).is_err());
+        assert!(roller.write(&[0, 0, 0, 3]).is_ok());
+        assert_eq!(roller.buffer, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_reject_when_full() {
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new()
+            .with_eviction_policy(EvictionPolicy::RejectWhenFull);
+
+        assert_eq!(roller.write(&[0, 0, 0, 1, 0, 0]).unwrap(), 6);
+        assert_eq!(roller.write(&[0, 2, 0, 0, 0, 3]).unwrap(), 2);
+        assert_eq!(roller.write(&[0, 0, 0, 3]).unwrap(), 0);
+        assert_eq!(roller.buffer, vec![1, 2]);
+        assert_eq!(roller.pending_bytes(), 0);
+
+        roller.clear();
+        assert_eq!(roller.write(&[0, 0, 0, 3]).unwrap(), 4);
+        assert_eq!(roller.buffer, vec![3]);
+    }
+
+    #[test]
+    fn drain() {
+        let mut roller: RollingStats<i32, BigEndian, 3> = (1..=4).collect();
+        let _ = roller.write(&[0, 0]).unwrap();
+        assert_eq!(roller.drain().collect::<Vec<_>>(), vec![2, 3, 4]);
+        assert!(roller.is_empty());
+        assert_eq!(roller.pending_bytes(), 2);
+
+        roller.extend([5, 6]);
+        let mut drain = roller.drain();
+        assert_eq!(drain.next(), Some(5));
+        drop(drain);
+        assert!(roller.is_empty());
+        assert_eq!(roller.evicted(), 1);
+    }
+
+    #[test]
+    fn callbacks() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (samples, evictions) = (events.clone(), events.clone());
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new()
+            .with_sample_callback(move |value| samples.lock().unwrap().push(*value))
+            .with_evict_callback(move |value| evictions.lock().unwrap().push(-*value));
+
+        let _ = roller.write(&[0, 0, 0, 1, 0, 0, 0, 2]).unwrap();
+        roller.extend_from_slice(&[3, 4, 5]).unwrap();
+        roller.clear();
+        assert_eq!(*events.lock().unwrap(), vec![1, 2, -1, 3, -2, 4, -3, 5]);
+    }
+
+    #[test]
+    fn clone_debug_and_eq() {
+        let mut roller = RollingStats::<i32, BigEndian, 10>::new().with_mode_tracking();
+        roller.extend(1..=10);
+        let snapshot = roller.clone();
+        assert_eq!(snapshot, roller);
+        assert_eq!(snapshot.mode(), roller.mode());
+
+        roller.push(11).unwrap();
+        assert_ne!(snapshot, roller);
+        assert_eq!(snapshot.buffer, (1..=10).collect::<Vec<_>>());
+        assert_eq!(
+            format!("{:?}", snapshot),
+            "RollingStats { window_size: 10, len: 10, items: [...2 more, 3, 4, 5, 6, 7, 8, 9, 10], \
+             pending_bytes: 0, total: 10, skipped: 0, evicted: 0 }"
+        );
+    }
+
+    #[test]
+    fn clear_resets_counters() {
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new()
+            .with_sentinels([0])
+            .with_trajectory(2)
+            .with_transform(|value| value * 10);
+        roller.extend([1, 0, 2, 3]);
+        let _ = roller.write(&[0, 0]).unwrap();
+        roller.clear();
+
+        assert!(roller.is_empty());
+        assert_eq!(roller.pending_bytes(), 0);
+        assert_eq!(roller.skipped(), 0);
+        assert_eq!(roller.evicted(), 0);
+        assert_eq!(roller.total_count(), 0);
+        assert!(roller.trajectory().is_empty());
+
+        roller.extend([4, 0]);
+        assert_eq!(roller.buffer, vec![40]);
+        assert_eq!(roller.skipped(), 1);
+    }
+
+    #[test]
+    fn test_conversion_error() {
+        let mut roller = RollingStats::<f32, RejectNan<LittleEndian>, 4>::new();
+        let data: Vec<u8> = [1.0f32, f32::NAN, 2.0]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        let error = roller.write(&data).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(roller.buffer, vec![1.0]);
+
+        let nan = f32::NAN.to_le_bytes();
+        let _ = roller.write(&nan[..2]).unwrap();
+        let error = roller.write(&nan[2..]).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(roller.pending_bytes(), 0);
+
+        let _ = roller.write(&3.0f32.to_le_bytes()).unwrap();
+        assert_eq!(roller.buffer, vec![1.0, 3.0]);
+    }
+
+    #[test]
+    fn test_write_vectored() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::default();
+        let bufs = [
+            IoSlice::new(&[0, 0]),
+            IoSlice::new(&[0, 1, 0, 0, 0]),
+            IoSlice::new(&[]),
+            IoSlice::new(&[2, 0, 0, 0, 3, 0]),
+        ];
+        assert_eq!(roller.write_vectored(&bufs).unwrap(), 13);
+        assert_eq!(roller.buffer, vec![1, 2, 3]);
+        assert_eq!(roller.pending_bytes(), 1);
+
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new()
+            .with_eviction_policy(EvictionPolicy::RejectWhenFull);
+        let bufs = [
+            IoSlice::new(&[0, 0, 0, 1, 0, 0]),
+            IoSlice::new(&[0, 2, 0, 0]),
+            IoSlice::new(&[0, 3]),
+        ];
+        assert_eq!(roller.write_vectored(&bufs).unwrap(), 8);
+        assert_eq!(roller.buffer, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_copy_and_buffered_writes() {
+        let data = [0u8, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0];
+
+        let mut roller = RollingStats::<i32, BigEndian, 3>::default();
+        assert_eq!(std::io::copy(&mut &data[..], &mut roller).unwrap(), 14);
+        assert_eq!(roller.len(), 3);
+        assert_eq!(roller.pending_bytes(), 2);
+
+        let mut writer =
+            std::io::BufWriter::with_capacity(3, RollingStats::<i32, BigEndian, 3>::default());
+        writer.write_all(&data).unwrap();
+        let roller = writer.into_inner().map_err(|_| ()).unwrap();
+        assert_eq!(roller.buffer, vec![1, 2, 3]);
+
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new()
+            .with_eviction_policy(EvictionPolicy::RejectWhenFull);
+        let error = std::io::copy(&mut &data[..], &mut roller).unwrap_err();
+        assert_eq!(error.kind(), std::io::ErrorKind::WriteZero);
+        assert_eq!(roller.buffer, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_runtime_width() {
+        let mut roller = RollingStats::<i64, RuntimeWidth<LittleEndian>, 3>::with_width(2);
+        let _ = roller.write(&[1, 0, 0xfe]).unwrap();
+
+        assert_eq!(roller.len(), 1);
+
+        let _ = roller.write(&[0xff, 4, 0, 5]).unwrap();
+
+        assert_eq!(roller.len(), 3);
+        assert_abs_diff_eq!(roller.mean(), 1.0);
+
+        assert!(matches!(
+            RollingStats::<i64, RuntimeWidth<LittleEndian>, 3>::try_with_width(9),
+            Err(Error::InvalidParameter(_))
+        ));
+    }
+
+    #[test]
+    fn test_f32_window() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::default();
+        let _ = roller
+            .write(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4])
+            .unwrap();
+
+        let mut window = roller.to_f32_window();
+        assert_eq!(window.buffer, vec![2.0, 3.0, 4.0]);
+        assert_abs_diff_eq!(window.mean(), 3.0);
+
+        let _ = window.write(&5.0f32.to_ne_bytes()).unwrap();
+        assert_abs_diff_eq!(window.mean(), 4.0);
+    }
+
+    #[test]
+    fn test_f64_precision() {
+        let mut roller = RollingStats::<i32, BigEndian, 3, f64>::default();
+        let _ = roller
+            .write(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4])
+            .unwrap();
+        assert_abs_diff_eq!(roller.mean(), 3.0f64);
+        assert_abs_diff_eq!(roller.std_dev(), 1.0f64);
+    }
+
+    #[derive(Clone, Default)]
+    struct SharedSink(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl Write for SharedSink {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sink() {
+        let sink = SharedSink::default();
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new().with_sink(sink.clone());
+        let _ = roller.write(&[0, 0, 0, 1, 0, 0, 0, 2, 0]).unwrap();
+
+        assert!(sink.0.lock().unwrap().is_empty());
+
+        let _ = roller.write(&[0, 0, 3, 0, 0, 0, 4]).unwrap();
+
+        assert_eq!(*sink.0.lock().unwrap(), vec![0, 0, 0, 1, 0, 0, 0, 2]);
+        assert_abs_diff_eq!(roller.mean(), 3.5);
+    }
 }
@@ -0,0 +1,64 @@
+//! Merging of the windows, such as combining the windows of several shards into a global view.
+
+use crate::{Error, EvictionPolicy, RollingStats, WindowStorage};
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    S: WindowStorage<T>,
+{
+    /// Appends the items of the other window after the items of this window, as if they were pushed to this window after its own items.
+    /// The items keep their order, the oldest items of this window are evicted once the window overflows, forwarded to the sink as usual,
+    /// so that only the newest items of the other window remain if it holds as many items as this window fits.
+    ///
+    /// The items of the other window were already accepted by it, so the filters and the transformation of this window are not applied to them,
+    /// the counters of this window are not changed except for the number of the evicted items.
+    /// Otherwise the items enter the window the same way the pushed values do - the anomalies are detected, the observers are notified,
+    /// and the trajectory and the tumbling windows are recorded.
+    /// # Errors
+    /// Returns the `Error::WindowFull` error once the window is full and the `EvictionPolicy::RejectWhenFull` policy is set,
+    /// or the error of the eviction sink, the items appended before the error are kept in the window.
+    pub fn merge(&mut self, other: &Self) -> Result<(), Error> {
+        for value in other.buffer.iter() {
+            if self.eviction_policy == EvictionPolicy::RejectWhenFull
+                && self.len() >= self.window_size
+            {
+                return Err(Error::WindowFull);
+            }
+
+            self.insert(*value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, EvictionPolicy, LittleEndian, RollingStats, Statistics};
+    use alloc::vec;
+
+    #[test]
+    fn merges_windows() {
+        let mut first: RollingStats<i32, LittleEndian, 4> = (1..=3).collect();
+        let second: RollingStats<i32, LittleEndian, 4> = (10..=12).collect();
+
+        first.merge(&second).unwrap();
+        assert_eq!(first.buffer, vec![3, 10, 11, 12]);
+        assert_eq!(first.evicted(), 2);
+        assert_eq!(first.mean(), 9.0);
+        assert_eq!(first.total_count(), 3);
+    }
+
+    #[test]
+    fn merge_rejected_when_full() {
+        let mut first = RollingStats::<i32, LittleEndian, 4>::new()
+            .with_eviction_policy(EvictionPolicy::RejectWhenFull);
+        first.extend(1..=3);
+        let second: RollingStats<i32, LittleEndian, 4> = (10..=12).collect();
+
+        assert!(matches!(first.merge(&second), Err(Error::WindowFull)));
+        assert_eq!(first.buffer, vec![1, 2, 3, 10]);
+        assert_eq!(first.evicted(), 0);
+    }
+}
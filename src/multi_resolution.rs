@@ -0,0 +1,149 @@
+//! Several windows of different lengths fed by one stream, such as the 1, 5 and 15 minute load averages.
+
+use crate::{
+    convertfloat::LossyFloatConvertible, report::summarize, ConverterFromRaw, DynRollingStats,
+    Error, Report,
+};
+use alloc::vec::Vec;
+use core::ops::Add;
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+
+/// Windows of several lengths sharing the parsed values, so that the raw data are parsed only once.
+///
+/// The values are stored in a single window as long as the longest resolution,
+/// the statistics of each resolution are computed over the newest values of that window.
+/// The configuration of the shared window, such as filters or the eviction sink, applies to all of the resolutions.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, MultiResolutionStats};
+///
+/// let mut stats = MultiResolutionStats::<i32, BigEndian>::new(&[1, 3]);
+/// stats.feed(&[0, 0, 0, 2, 0, 0, 0, 4, 0, 0, 0, 9]).unwrap();
+///
+/// let means: Vec<(usize, f32)> = stats.reports().map(|(len, report)| (len, report.mean)).collect();
+/// assert_eq!(means, vec![(1, 9.0), (3, 5.0)]);
+/// ```
+pub struct MultiResolutionStats<T, E, O = f32> {
+    window: DynRollingStats<T, E, O>,
+    resolutions: Vec<usize>,
+}
+
+impl<T, E, O> MultiResolutionStats<T, E, O> {
+    /// Creates the windows of the given lengths, the reports are returned in the same order.
+    pub fn new(resolutions: &[usize]) -> Self {
+        Self::with_window(DynRollingStats::new(), resolutions)
+    }
+
+    /// Creates the windows of the given lengths sharing the configured window, such as one created by the `builder`.
+    /// The window is resized to the longest of the resolutions.
+    pub fn with_window(mut window: DynRollingStats<T, E, O>, resolutions: &[usize]) -> Self {
+        window.window_size = resolutions.iter().copied().max().unwrap_or(0);
+        window.buffer.clear();
+        Self {
+            window,
+            resolutions: resolutions.to_vec(),
+        }
+    }
+
+    /// Returns the lengths of the windows.
+    pub fn resolutions(&self) -> &[usize] {
+        &self.resolutions
+    }
+
+    /// Returns the shared window, as long as the longest resolution.
+    pub fn window(&self) -> &DynRollingStats<T, E, O> {
+        &self.window
+    }
+
+    /// Returns the shared window mutably, such as to push already decoded values.
+    /// Resizing the window shortens the history available to the longer resolutions.
+    pub fn window_mut(&mut self) -> &mut DynRollingStats<T, E, O> {
+        &mut self.window
+    }
+}
+
+impl<T, E, O> MultiResolutionStats<T, E, O>
+where
+    T: Copy,
+    E: ConverterFromRaw<T>,
+{
+    /// Parses the raw data into the shared window, the same way the `feed` method of the window does.
+    pub fn feed(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        self.window.feed(buf)
+    }
+}
+
+impl<T, E, O> MultiResolutionStats<T, E, O>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+{
+    /// Returns the report of the window of the given length, computed over its newest values,
+    /// or `None` if the length is not one of the resolutions.
+    pub fn report(&self, resolution: usize) -> Option<Report<O>> {
+        if !self.resolutions.contains(&resolution) {
+            return None;
+        }
+
+        Some(self.summarize(resolution))
+    }
+
+    /// Returns the pairs of the length of each window and its report, in the order of the resolutions.
+    pub fn reports(&self) -> impl Iterator<Item = (usize, Report<O>)> + '_ {
+        self.resolutions
+            .iter()
+            .map(move |&resolution| (resolution, self.summarize(resolution)))
+    }
+
+    /// Creates the report of the newest `resolution` values of the shared window.
+    fn summarize(&self, resolution: usize) -> Report<O> {
+        let skipped = self.window.len().saturating_sub(resolution);
+        summarize(
+            self.window.buffer.iter().skip(skipped).copied(),
+            self.window.ddof,
+            self.window.decay,
+        )
+    }
+}
+
+/// Writes the raw data into the shared window, see the `std::io::Write` implementation of the `RollingStats`.
+#[cfg(feature = "std")]
+impl<T, E, O> std::io::Write for MultiResolutionStats<T, E, O>
+where
+    T: Copy,
+    E: ConverterFromRaw<T>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.window.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.window.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LittleEndian, RollingStats, Statistics};
+
+    #[test]
+    fn shares_values() {
+        let mut stats = MultiResolutionStats::<i32, LittleEndian>::new(&[2, 4, 8]);
+        let mut short = RollingStats::<i32, LittleEndian, 4>::new();
+        for value in [1i32, 5, 2, 8, 4, 7] {
+            stats.feed(&value.to_le_bytes()).unwrap();
+            short.feed(&value.to_le_bytes()).unwrap();
+        }
+
+        assert_eq!(stats.window().window_size(), 8);
+        let report = stats.report(4).unwrap();
+        assert_eq!(report, short.report());
+        assert_eq!(report.std_dev, short.std_dev());
+        assert_eq!(stats.report(2).unwrap().mean, 5.5);
+        assert_eq!(stats.report(8).unwrap().len, 6);
+        assert_eq!(stats.report(3), None);
+        assert_eq!(stats.reports().count(), 3);
+    }
+}
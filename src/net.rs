@@ -0,0 +1,364 @@
+//! Ingestion of the raw data received over the network, such as the packets sent by sensor gateways.
+
+use crate::{Error, Metric, Registry};
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs, UdpSocket},
+    time::Duration,
+};
+
+/// The largest possible payload of a UDP datagram.
+const MAX_DATAGRAM_SIZE: usize = 65536;
+
+/// The default size of the chunks the TCP stream is read in.
+const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+/// Receives UDP datagrams, feeding each of the datagrams to a window, such as the `RollingStats`.
+/// The datagrams are messages of whole items, the trailing bytes of a datagram not forming a whole item are discarded
+/// rather than completed by the following datagram, and the datagram is counted by the `truncated` method.
+/// # Examples
+/// ```no_run
+/// use rolling_stats::{LittleEndian, RollingStats, Statistics, UdpIngest};
+///
+/// let mut ingest = UdpIngest::bind("0.0.0.0:5000").unwrap();
+/// let mut roller = RollingStats::<i32, LittleEndian, 100>::new();
+/// loop {
+///     ingest.receive(&mut roller).unwrap();
+///     println!("{}", roller.mean());
+/// }
+/// ```
+pub struct UdpIngest {
+    socket: UdpSocket,
+    buf: Vec<u8>,
+    truncated: usize,
+    refused: usize,
+}
+
+impl UdpIngest {
+    /// Creates a new `UdpIngest` receiving the datagrams sent to the address.
+    pub fn bind<A: ToSocketAddrs>(addr: A) -> Result<Self, Error> {
+        Ok(Self::from_socket(UdpSocket::bind(addr)?))
+    }
+
+    /// Creates a new `UdpIngest` receiving the datagrams using an already configured socket.
+    pub fn from_socket(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            buf: vec![0; MAX_DATAGRAM_SIZE],
+            truncated: 0,
+            refused: 0,
+        }
+    }
+
+    /// Returns the socket, such as for setting the read timeout.
+    pub fn socket(&self) -> &UdpSocket {
+        &self.socket
+    }
+
+    /// Returns the number of the datagrams whose trailing bytes didn't form a whole item and were discarded.
+    pub fn truncated(&self) -> usize {
+        self.truncated
+    }
+
+    /// Waits for a datagram and feeds it to the window.
+    /// # Returns
+    /// Returns the address of the sender of the datagram.
+    pub fn receive<O, M>(&mut self, metric: &mut M) -> Result<SocketAddr, Error>
+    where
+        M: Metric<O> + ?Sized,
+    {
+        let (len, source) = self.socket.recv_from(&mut self.buf)?;
+        self.feed(&mut *metric, len)?;
+
+        Ok(source)
+    }
+
+    /// Feeds the first `len` bytes of the buffer to the window as a whole message.
+    fn feed<O, M>(&mut self, metric: &mut M, len: usize) -> Result<(), Error>
+    where
+        M: Metric<O> + ?Sized,
+    {
+        let result = metric.feed(&self.buf[..len]);
+        if metric.discard_pending() > 0 {
+            self.truncated += 1;
+        }
+
+        result.map(|_| ())
+    }
+
+    /// Returns the number of the datagrams discarded as the `new` closure of the `receive_per_source` method refused their senders.
+    pub fn refused(&self) -> usize {
+        self.refused
+    }
+
+    /// Waits for a datagram and feeds it to the window of its sender registered in the registry,
+    /// named by the address of the sender, such as `192.168.1.10:5000`, so that the data of each of the senders are kept apart.
+    ///
+    /// The window of a new sender is created using the `new` closure, the datagram is discarded and counted by the `refused` method if it returns `None`.
+    /// The source addresses of the datagrams can be spoofed, so unless the closure limits the senders,
+    /// such as to a known set of addresses or to a number of the registered windows, the registry grows with every new address.
+    /// # Returns
+    /// Returns the address of the sender of the datagram.
+    pub fn receive_per_source<O, M, F>(
+        &mut self,
+        registry: &mut Registry<O>,
+        new: F,
+    ) -> Result<SocketAddr, Error>
+    where
+        M: Metric<O> + Send + 'static,
+        F: FnOnce(SocketAddr, &Registry<O>) -> Option<M>,
+    {
+        let (len, source) = self.socket.recv_from(&mut self.buf)?;
+        let name = source.to_string();
+        if registry.get(&name).is_none() {
+            match new(source, registry) {
+                Some(metric) => {
+                    registry.register(&name, metric);
+                }
+                None => {
+                    self.refused += 1;
+                    return Ok(source);
+                }
+            }
+        }
+
+        if let Some(metric) = registry.get_mut(&name) {
+            self.feed(metric, len)?;
+        }
+
+        Ok(source)
+    }
+}
+
+/// Denotes the way the TCP connection is established.
+enum TcpSource {
+    /// Connects out to the first reachable address.
+    Connect(Vec<SocketAddr>),
+    /// Accepts incoming connections, one at a time.
+    Accept(TcpListener),
+}
+
+/// Reads a TCP stream, writing the received raw data to a writer, such as the `RollingStats`.
+/// The segmentation of the stream is arbitrary, it is the partial data handling of the writer that reconstructs the items.
+///
+/// By default, the reading ends once the connection is closed. Reconnecting can be enabled using the `with_reconnect` method,
+/// incomplete data received before the connection was lost are not discarded, so the peers are expected to send whole items.
+pub struct TcpIngest {
+    source: TcpSource,
+    chunk_size: usize,
+    reconnect: Option<Duration>,
+}
+
+impl TcpIngest {
+    /// Creates a new `TcpIngest` connecting out to the address.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> Result<Self, Error> {
+        Ok(Self::new(TcpSource::Connect(
+            addr.to_socket_addrs()?.collect(),
+        )))
+    }
+
+    /// Creates a new `TcpIngest` accepting the connections of the listener.
+    pub fn accept(listener: TcpListener) -> Self {
+        Self::new(TcpSource::Accept(listener))
+    }
+
+    fn new(source: TcpSource) -> Self {
+        Self {
+            source,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            reconnect: None,
+        }
+    }
+
+    /// Sets the size of the chunks the stream is read in, 4096 bytes by default.
+    /// # Panics
+    /// Panics if the chunk size is zero.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        assert!(chunk_size > 0, "The chunk size has to be non-zero.");
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Enables reconnecting - once the connection is closed or fails, or the connecting fails, a new connection is established after the delay.
+    pub fn with_reconnect(mut self, delay: Duration) -> Self {
+        self.reconnect = Some(delay);
+        self
+    }
+
+    /// Reads the stream, writing the received raw data to the writer.
+    /// After each chunk, the `keep_running` closure is called with the number of bytes received, the reading ends once it returns `false`.
+    /// # Returns
+    /// Returns the total number of bytes received.
+    /// # Errors
+    /// Returns the errors of the writer right away, and the errors of the connection unless reconnecting is enabled.
+    pub fn run<W, F>(&mut self, writer: &mut W, mut keep_running: F) -> Result<u64, Error>
+    where
+        W: Write,
+        F: FnMut(usize) -> bool,
+    {
+        let mut buf = vec![0u8; self.chunk_size];
+        let mut total = 0;
+        loop {
+            // the errors of the connection are kept apart from the errors of the writer, which are never retried
+            let result = self.open().and_then(|mut stream| loop {
+                let read = match stream.read(&mut buf) {
+                    Ok(0) => return Ok(Ok(true)),
+                    Ok(read) => read,
+                    Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(error) => return Err(error),
+                };
+                total += read as u64;
+                if let Err(error) = writer.write_all(&buf[..read]) {
+                    return Ok(Err(error));
+                }
+                if !keep_running(read) {
+                    return Ok(Ok(false));
+                }
+            });
+
+            match (result, self.reconnect) {
+                (Ok(Err(error)), _) => return Err(error.into()),
+                (Ok(Ok(false)), _) | (Ok(Ok(true)), None) => return Ok(total),
+                (Err(error), None) => return Err(error.into()),
+                (_, Some(delay)) => std::thread::sleep(delay),
+            }
+        }
+    }
+
+    /// Establishes a new connection.
+    fn open(&self) -> std::io::Result<TcpStream> {
+        match &self.source {
+            TcpSource::Connect(addrs) => TcpStream::connect(&addrs[..]),
+            TcpSource::Accept(listener) => listener.accept().map(|(stream, _)| stream),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BigEndian, RawConversionError, RejectNan, RollingStats};
+
+    #[test]
+    fn receives_datagrams() {
+        let mut ingest = UdpIngest::bind("127.0.0.1:0").unwrap();
+        let address = ingest.socket().local_addr().unwrap();
+        let first = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let second = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        let mut roller = RollingStats::<i32, BigEndian, 4>::new();
+        first.send_to(&[0, 0, 0, 1, 0, 0], address).unwrap();
+        first.send_to(&[0, 2], address).unwrap();
+        first.send_to(&[0, 0, 0, 3], address).unwrap();
+        assert_eq!(
+            ingest.receive(&mut roller).unwrap(),
+            first.local_addr().unwrap()
+        );
+        let _ = ingest.receive(&mut roller).unwrap();
+        let _ = ingest.receive(&mut roller).unwrap();
+        assert_eq!(roller.buffer, vec![1, 3]);
+        assert_eq!(roller.pending_bytes(), 0);
+        assert_eq!(ingest.truncated(), 2);
+
+        let mut registry = Registry::new();
+        first.send_to(&[0, 0, 0, 3], address).unwrap();
+        second.send_to(&[0, 0, 0, 4], address).unwrap();
+        second.send_to(&[0, 0, 0, 6], address).unwrap();
+        for _ in 0..3 {
+            let _ = ingest
+                .receive_per_source(&mut registry, |_, registry| {
+                    Some(RollingStats::<i32, BigEndian, 4>::new()).filter(|_| registry.len() < 2)
+                })
+                .unwrap();
+        }
+        let mean = |source: &UdpSocket| {
+            let name = source.local_addr().unwrap().to_string();
+            registry.report(&name).unwrap().mean
+        };
+        assert_eq!(mean(&first), 3.0);
+        assert_eq!(mean(&second), 5.0);
+        assert_eq!(ingest.refused(), 0);
+
+        let third = UdpSocket::bind("127.0.0.1:0").unwrap();
+        third.send_to(&[0, 0, 0, 7], address).unwrap();
+        let _ = ingest
+            .receive_per_source(&mut registry, |_, registry| {
+                Some(RollingStats::<i32, BigEndian, 4>::new()).filter(|_| registry.len() < 2)
+            })
+            .unwrap();
+        assert_eq!(registry.len(), 2);
+        assert_eq!(ingest.refused(), 1);
+    }
+
+    #[test]
+    fn reads_stream() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let sender = std::thread::spawn(move || {
+            for segments in [[&[0u8, 0, 0][..], &[1, 0, 0, 0, 2, 0]], [&[0, 0, 3], &[]]] {
+                let mut stream = TcpStream::connect(address).unwrap();
+                for segment in segments {
+                    stream.write_all(segment).unwrap();
+                    stream.flush().unwrap();
+                }
+            }
+        });
+
+        let mut roller = RollingStats::<i32, BigEndian, 4>::new();
+        let mut ingest = TcpIngest::accept(listener).with_chunk_size(3);
+        assert_eq!(ingest.run(&mut roller, |_| true).unwrap(), 9);
+        assert_eq!(roller.buffer, vec![1, 2]);
+
+        let mut ingest = ingest.with_reconnect(Duration::from_millis(1));
+        let mut received = 0;
+        let total = ingest
+            .run(&mut roller, |read| {
+                received += read;
+                received < 3
+            })
+            .unwrap();
+        sender.join().unwrap();
+
+        assert_eq!(total, 3);
+        assert_eq!(roller.buffer, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn returns_writer_error_when_reconnecting() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let sender = std::thread::spawn(move || {
+            let mut stream = TcpStream::connect(address).unwrap();
+            stream.write_all(&f32::NAN.to_be_bytes()).unwrap();
+        });
+
+        let mut roller = RollingStats::<f32, RejectNan<BigEndian>, 4>::new();
+        let mut ingest = TcpIngest::accept(listener).with_reconnect(Duration::from_millis(1));
+        let error = ingest.run(&mut roller, |_| true).unwrap_err();
+        sender.join().unwrap();
+
+        assert!(matches!(
+            error,
+            Error::Conversion(RawConversionError::NotANumber)
+        ));
+    }
+
+    #[test]
+    fn connects_out() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let sender = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.write_all(&[0, 0, 0, 5]).unwrap();
+        });
+
+        let mut roller = RollingStats::<i32, BigEndian, 4>::new();
+        let _ = TcpIngest::connect(address)
+            .unwrap()
+            .run(&mut roller, |_| true)
+            .unwrap();
+        sender.join().unwrap();
+
+        assert_eq!(roller.buffer, vec![5]);
+    }
+}
@@ -0,0 +1,157 @@
+//! Observers of the events of the window, such as keeping auxiliary structures in sync with the window, alarming or logging.
+
+use crate::{RawConversionError, RollingStats, WindowStorage};
+use alloc::boxed::Box;
+
+/// Receives the events of the window it is attached to using the `with_observer` method.
+/// All of the methods do nothing by default, so that an observer implements only the events it is interested in.
+pub trait Observer<T> {
+    /// Called with every value entering the window, after the value is pushed to the window.
+    fn on_sample(&mut self, value: &T) {
+        let _ = value;
+    }
+
+    /// Called with every value evicted from the window, before the value is forwarded to the eviction sink.
+    /// The values removed by the `clear` or the `drain` method are not evicted.
+    fn on_evict(&mut self, value: &T) {
+        let _ = value;
+    }
+
+    /// Called once the window becomes full, after the value filling the window is pushed.
+    fn on_full(&mut self) {}
+
+    /// Called when the raw data can't be converted into a value, before the error is returned from the write.
+    fn on_conversion_error(&mut self, error: &RawConversionError) {
+        let _ = error;
+    }
+
+    /// Called when the strict alignment mode discards the `discarded` bytes of partial data to resynchronize with the stream.
+    fn on_resync(&mut self, discarded: usize) {
+        let _ = discarded;
+    }
+}
+
+/// An observer calling the closure with the values entering the window.
+struct SampleCallback<F>(F);
+
+impl<T, F: FnMut(&T)> Observer<T> for SampleCallback<F> {
+    fn on_sample(&mut self, value: &T) {
+        (self.0)(value)
+    }
+}
+
+/// An observer calling the closure with the values evicted from the window.
+struct EvictCallback<F>(F);
+
+impl<T, F: FnMut(&T)> Observer<T> for EvictCallback<F> {
+    fn on_evict(&mut self, value: &T) {
+        (self.0)(value)
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Attaches an observer to the window, the observers receive the events in the order they were attached.
+    /// The observers are not cloned along with the window.
+    pub fn with_observer<B>(mut self, observer: B) -> Self
+    where
+        B: Observer<T> + Send + 'static,
+    {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Sets a callback invoked with every value entering the window, after the value is pushed to the window,
+    /// such as to keep an auxiliary structure in sync with the window.
+    /// A shorthand for an observer implementing only the `on_sample` method.
+    pub fn with_sample_callback<F>(self, callback: F) -> Self
+    where
+        F: FnMut(&T) + Send + 'static,
+    {
+        self.with_observer(SampleCallback(callback))
+    }
+
+    /// Sets a callback invoked with every value evicted from the window, before the value is forwarded to the sink.
+    /// The values removed by the `clear` or the `drain` method are not evicted, so the callback is not invoked for them.
+    /// A shorthand for an observer implementing only the `on_evict` method.
+    pub fn with_evict_callback<F>(self, callback: F) -> Self
+    where
+        F: FnMut(&T) + Send + 'static,
+    {
+        self.with_observer(EvictCallback(callback))
+    }
+
+    /// Passes the event to all of the observers.
+    pub(crate) fn notify<F>(&mut self, mut event: F)
+    where
+        F: FnMut(&mut dyn Observer<T>),
+    {
+        for observer in self.observers.iter_mut() {
+            event(observer.as_mut());
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{BigEndian, RawConversionError, RejectNan};
+    use std::{
+        io::Write,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Clone, Default)]
+    struct Events(Arc<Mutex<Vec<String>>>);
+
+    impl Observer<f32> for Events {
+        fn on_sample(&mut self, value: &f32) {
+            self.0.lock().unwrap().push(format!("sample {}", value));
+        }
+
+        fn on_evict(&mut self, value: &f32) {
+            self.0.lock().unwrap().push(format!("evict {}", value));
+        }
+
+        fn on_full(&mut self) {
+            self.0.lock().unwrap().push("full".to_string());
+        }
+
+        fn on_conversion_error(&mut self, error: &RawConversionError) {
+            self.0.lock().unwrap().push(format!("error {:?}", error));
+        }
+
+        fn on_resync(&mut self, discarded: usize) {
+            self.0.lock().unwrap().push(format!("resync {}", discarded));
+        }
+    }
+
+    #[test]
+    fn observes_events() {
+        let events = Events::default();
+        let mut roller = RollingStats::<f32, RejectNan<BigEndian>, 2>::new()
+            .with_strict_alignment(0)
+            .with_observer(events.clone());
+
+        roller.write_all(&1f32.to_be_bytes()).unwrap();
+        roller.write_all(&2f32.to_be_bytes()).unwrap();
+        roller.write_all(&3f32.to_be_bytes()).unwrap();
+        assert!(roller.write(&f32::NAN.to_be_bytes()).is_err());
+        assert!(roller.write(&[0]).is_err());
+
+        assert_eq!(
+            *events.0.lock().unwrap(),
+            vec![
+                "sample 1",
+                "sample 2",
+                "full",
+                "evict 1",
+                "sample 3",
+                "error NotANumber",
+                "resync 1"
+            ]
+        );
+    }
+}
@@ -0,0 +1,153 @@
+//! Order statistics of the window - queries based on ordering the items currently stored in the window.
+
+use crate::{convertfloat::LossyFloatConvertible, Error, RollingStats, WindowStorage};
+use alloc::{collections::BinaryHeap, vec::Vec};
+use core::cmp::{Ordering, Reverse};
+use rand_distr::num_traits::Float;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Ord,
+    S: WindowStorage<T>,
+{
+    /// Returns the `n` largest items in the window, sorted from the largest one.
+    /// `n` is clamped to the number of items in the window.
+    ///
+    /// The selection is done using a binary heap bounded to `n` items, so the whole window is not sorted.
+    pub fn n_largest(&self, n: usize) -> Vec<T> {
+        let mut heap = BinaryHeap::with_capacity(n.min(self.len()) + 1);
+        for item in self.buffer.iter() {
+            heap.push(Reverse(*item));
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+
+    /// Returns the `n` smallest items in the window, sorted from the smallest one.
+    /// `n` is clamped to the number of items in the window.
+    ///
+    /// The selection is done using a binary heap bounded to `n` items, so the whole window is not sorted.
+    pub fn n_smallest(&self, n: usize) -> Vec<T> {
+        let mut heap = BinaryHeap::with_capacity(n.min(self.len()) + 1);
+        for item in self.buffer.iter() {
+            heap.push(*item);
+            if heap.len() > n {
+                heap.pop();
+            }
+        }
+
+        heap.into_sorted_vec()
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + PartialOrd + LossyFloatConvertible<O>,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns the `p`-th percentile of the window, `p` being clamped to the range from 0 to 100.
+    /// Percentiles falling between two items are linearly interpolated.
+    /// # Errors
+    /// Returns the `Error::EmptyWindow` error for an empty window.
+    pub fn percentile(&self, p: O) -> Result<O, Error> {
+        if self.is_empty() {
+            return Err(Error::EmptyWindow);
+        }
+
+        let mut sorted: Vec<T> = self.buffer.iter().copied().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let sorted: Vec<O> = sorted.iter().map(|item| item.convert()).collect();
+
+        let hundred = O::from(100).unwrap();
+        let position = p.max(O::zero()).min(hundred) / hundred * O::from(sorted.len() - 1).unwrap();
+        Ok(interpolate(&sorted, position))
+    }
+
+    /// Returns the historical Value-at-Risk of a window of returns at the specified confidence level (e.g. `0.95`),
+    /// i.e. the negated `1 - confidence` percentile of the window - the 95% confidence level corresponds to the 5th percentile.
+    /// Following the financial sign convention, a loss is returned as a positive value and a gain at the percentile as a negative one.
+    /// # Errors
+    /// Returns the `Error::EmptyWindow` error for an empty window.
+    pub fn value_at_risk(&self, confidence: O) -> Result<O, Error> {
+        self.percentile((O::one() - confidence) * O::from(100).unwrap())
+            .map(|percentile| -percentile)
+    }
+}
+
+/// Returns the value at a fractional index of the values, linearly interpolating between the neighbouring values.
+/// The position is clamped to the valid indices, zero is returned for no values.
+pub(crate) fn interpolate<O: Float>(values: &[O], position: O) -> O {
+    if values.is_empty() {
+        return O::zero();
+    }
+
+    let position = position
+        .max(O::zero())
+        .min(O::from(values.len() - 1).unwrap());
+    let lower = position.floor();
+    let index = lower.to_usize().unwrap();
+    let fraction = position - lower;
+
+    match values.get(index + 1) {
+        Some(upper) if !fraction.is_zero() => values[index] + (*upper - values[index]) * fraction,
+        _ => values[index],
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{BigEndian, Error, RollingStats};
+    use approx::*;
+    use std::io::Write;
+
+    #[test]
+    fn n_largest_and_smallest() {
+        let mut roller = RollingStats::<i32, BigEndian, 5>::default();
+        let _ = roller
+            .write(&[0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0, 2, 0, 0, 0, 4])
+            .unwrap();
+
+        assert_eq!(roller.n_largest(2), vec![5, 4]);
+        assert_eq!(roller.n_smallest(3), vec![1, 2, 3]);
+        assert_eq!(roller.n_largest(10), vec![5, 4, 3, 2, 1]);
+        assert!(roller.n_smallest(0).is_empty());
+    }
+
+    #[test]
+    fn percentile() {
+        let mut roller = RollingStats::<i32, BigEndian, 5>::default();
+        assert!(matches!(roller.percentile(50.0), Err(Error::EmptyWindow)));
+
+        let _ = roller
+            .write(&[0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0, 2, 0, 0, 0, 4])
+            .unwrap();
+
+        assert_abs_diff_eq!(roller.percentile(0.0).unwrap(), 1.0);
+        assert_abs_diff_eq!(roller.percentile(50.0).unwrap(), 3.0);
+        assert_abs_diff_eq!(roller.percentile(90.0).unwrap(), 4.6);
+        assert_abs_diff_eq!(roller.percentile(150.0).unwrap(), 5.0);
+    }
+
+    #[test]
+    fn value_at_risk() {
+        let mut roller = RollingStats::<i32, BigEndian, 5>::default();
+        assert!(matches!(
+            roller.value_at_risk(0.95),
+            Err(Error::EmptyWindow)
+        ));
+
+        for value in [-10i32, -5, 0, 5, 10] {
+            let _ = roller.write(&value.to_be_bytes()).unwrap();
+        }
+
+        assert_abs_diff_eq!(roller.value_at_risk(0.95).unwrap(), 9.0, epsilon = 1e-5);
+        assert_abs_diff_eq!(roller.value_at_risk(0.5).unwrap(), 0.0);
+    }
+}
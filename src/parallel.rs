@@ -0,0 +1,119 @@
+//! Parallel parsing of large batches of raw data, such as when replaying multi-gigabyte captures.
+
+use crate::{
+    ConverterFromRaw, Error, EvictionPolicy, RawConversionError, RollingStats, WindowStorage,
+};
+use std::io::Write;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Send,
+    E: ConverterFromRaw<T>,
+    S: WindowStorage<T>,
+{
+    /// Writes the raw data the same way the `write` method does, parsing the raw data on up to `threads` threads.
+    /// The raw data are split into chunks of whole items, the parsed values are then pushed to the window in order.
+    /// Worth it only for batches much larger than the window, falls back to the `write` method for a single thread
+    /// or if the window rejects new values once full.
+    /// # Returns
+    /// Returns the number of consumed bytes, or the error of the parsing, in which case the values preceding the failed one are kept in the window.
+    pub fn write_parallel(&mut self, buf: &[u8], threads: usize) -> Result<usize, Error> {
+        if threads <= 1 || self.eviction_policy == EvictionPolicy::RejectWhenFull {
+            return Ok(self.write(buf)?);
+        }
+
+        let type_size = self.type_size();
+        let leading = ((type_size - self.pending_bytes()) % type_size).min(buf.len());
+        self.write_raw(&buf[..leading])?;
+
+        let buf = &buf[leading..];
+        let aligned = buf.len() - buf.len() % type_size;
+        let chunk_size = (aligned / type_size).div_ceil(threads).max(1) * type_size;
+        let parsed: Vec<(Vec<T>, Option<RawConversionError>)> = std::thread::scope(|scope| {
+            let workers: Vec<_> = buf[..aligned]
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || parse_chunk::<T, E>(chunk, type_size)))
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|worker| {
+                    worker
+                        .join()
+                        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+                })
+                .collect()
+        });
+
+        for (values, error) in parsed {
+            for value in values {
+                self.push(value)?;
+            }
+            if let Some(error) = error {
+                self.notify(|observer| observer.on_conversion_error(&error));
+                return Err(error.into());
+            }
+        }
+
+        self.write_raw(&buf[aligned..])?;
+        self.check_alignment()?;
+
+        Ok(leading + buf.len())
+    }
+}
+
+/// Parses the chunk of whole items, stopping at the first conversion error.
+fn parse_chunk<T, E>(chunk: &[u8], type_size: usize) -> (Vec<T>, Option<RawConversionError>)
+where
+    E: ConverterFromRaw<T>,
+{
+    let mut values = Vec::with_capacity(chunk.len() / type_size);
+    for raw in chunk.chunks_exact(type_size) {
+        match E::from_raw(raw) {
+            Ok(value) => values.push(value),
+            Err(error) => return (values, Some(error)),
+        }
+    }
+
+    (values, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, Error, LittleEndian, RejectNan, RollingStats};
+    use std::io::Write;
+
+    #[test]
+    fn matches_sequential_write() {
+        let data: Vec<u8> = (0..10_001i32).flat_map(|v| v.to_be_bytes()).collect();
+
+        let mut sequential = RollingStats::<i32, BigEndian, 100>::new();
+        let _ = sequential.write(&data[..3]).unwrap();
+        let _ = sequential.write(&data[3..]).unwrap();
+
+        let mut parallel = RollingStats::<i32, BigEndian, 100>::new();
+        let _ = parallel.write(&data[..3]).unwrap();
+        assert_eq!(
+            parallel.write_parallel(&data[3..39_999], 4).unwrap(),
+            39_996
+        );
+        let _ = parallel.write_parallel(&data[39_999..], 4).unwrap();
+
+        assert_eq!(parallel.buffer, sequential.buffer);
+        assert_eq!(parallel.total_count(), 10_001);
+        assert_eq!(parallel.pending_bytes(), 0);
+    }
+
+    #[test]
+    fn keeps_values_preceding_error() {
+        let mut values = vec![1.0f32; 1000];
+        values[500] = f32::NAN;
+        let data: Vec<u8> = values.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+        let mut roller = RollingStats::<f32, RejectNan<LittleEndian>, 1000>::new();
+        let error = roller.write_parallel(&data, 8).unwrap_err();
+
+        assert!(matches!(error, Error::Conversion(_)));
+        assert_eq!(roller.len(), 500);
+    }
+}
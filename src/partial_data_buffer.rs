@@ -6,8 +6,9 @@
 //!
 //! As opposed to the `Reconstructor`, this solution avoids pointless copies.
 
-use crate::ConverterFromRaw;
-use std::marker::PhantomData;
+use crate::{ConverterFromRaw, RawConversionError};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 /// Implements the partial data buffer - handling of incomplete data in a stream of raw data.
 /// # Type parameteres
@@ -17,17 +18,53 @@ pub struct PartialDataBuffer<T, E> {
     _e: PhantomData<E>,
     _t: PhantomData<T>,
     buffer: Vec<u8>,
+    type_size: usize,
 }
 
 impl<T, E> Default for PartialDataBuffer<T, E> {
     /// Creates an empty buffer.
     fn default() -> Self {
+        Self::with_type_size(core::mem::size_of::<T>())
+    }
+}
+
+impl<T, E> Clone for PartialDataBuffer<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            _e: PhantomData,
+            _t: PhantomData,
+            buffer: self.buffer.clone(),
+            type_size: self.type_size,
+        }
+    }
+}
+
+impl<T, E> PartialDataBuffer<T, E> {
+    /// Creates an empty buffer for raw data of items `type_size` bytes long, regardless of the size of `T`.
+    pub fn with_type_size(type_size: usize) -> Self {
         Self {
             _e: PhantomData,
             _t: PhantomData,
             buffer: Vec::new(),
+            type_size,
         }
     }
+
+    /// Returns the number of bytes of the incomplete data currently stored in the inner buffer.
+    pub fn pending_bytes(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Clears the inner buffer, discarding the contained data.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+    }
+
+    /// Returns the size in bytes of the type meant to be reconstructed from the raw data,
+    /// the size of `T` unless specified otherwise.
+    pub fn type_size(&self) -> usize {
+        self.type_size
+    }
 }
 
 impl<T, E> PartialDataBuffer<T, E>
@@ -36,13 +73,17 @@ where
     T: Clone,
 {
     /// Consumes the input slice of raw data, if enough data is present to reconstruct the partially received data, the data is and returned.
+    /// The conversion of the reconstructed data may fail, in which case the conversion error is returned in its place.
     /// The raw data slice is stripped off of the leading bytes belonging to the previously received incomplete data, any trailing partial data is stored to the internal buffer.
     ///
     /// # Returns
     /// Returns a slice constructed by removing partial data from the raw data stream.
     /// The returned slice is free of both the leading and trailing partial data.
     /// The returned slice contains a an integer of the target type lengths.
-    pub fn consume<'a>(&mut self, raw: &'a [u8]) -> (Option<T>, &'a [u8]) {
+    pub fn consume<'a>(
+        &mut self,
+        raw: &'a [u8],
+    ) -> (Option<Result<T, RawConversionError>>, &'a [u8]) {
         if self.buffer.len() + raw.len() < self.type_size() {
             self.buffer.extend(raw);
             return (None, &[]);
@@ -56,7 +97,7 @@ where
 
         let reconstructed_value = if offset > 0 {
             self.buffer.extend(&raw[..offset]);
-            let result = E::from_raw(&self.buffer).unwrap();
+            let result = E::from_raw(&self.buffer);
             self.clear();
             Some(result)
         } else {
@@ -70,16 +111,6 @@ where
 
         (reconstructed_value, &raw[offset..(raw.len() - remainder)])
     }
-
-    /// Clears the inner buffer, discarding the contained data.
-    pub fn clear(&mut self) {
-        self.buffer.clear();
-    }
-
-    /// Returns the size in bytes of the type meant to be reconstructed from the raw data,
-    pub fn type_size(&self) -> usize {
-        std::mem::size_of::<T>()
-    }
 }
 
 #[cfg(test)]
@@ -95,30 +126,30 @@ mod tests {
         let (item, rest) = buffer.consume(&data);
         assert!(item.is_none());
         assert_eq!(rest, &[]);
-        assert_eq!(buffer.buffer.len(), 2);
+        assert_eq!(buffer.pending_bytes(), 2);
 
         let data = [0x00, 0x00];
         let (item, rest) = buffer.consume(&data);
         assert!(item.is_some());
         assert_eq!(rest, &[]);
-        assert_eq!(buffer.buffer.len(), 0);
+        assert_eq!(buffer.pending_bytes(), 0);
 
         let data = [0x01, 0x00, 0x00, 0x00, 0x02, 0x00];
         let (item, rest) = buffer.consume(&data);
         assert!(item.is_none());
         assert_eq!(rest.len(), 4);
-        assert_eq!(buffer.buffer.len(), 2);
+        assert_eq!(buffer.pending_bytes(), 2);
 
         let data = [0x01, 0x00, 0x00, 0x00, 0x02, 0x00];
         let (item, rest) = buffer.consume(&data);
         assert!(item.is_some());
         assert_eq!(rest.len(), 4);
-        assert_eq!(buffer.buffer.len(), 0);
+        assert_eq!(buffer.pending_bytes(), 0);
 
         let data = [0x01, 0x00, 0x00, 0x00, 0x02, 0x00];
         let (item, rest) = buffer.consume(&data);
         assert!(item.is_none());
         assert_eq!(rest.len(), 4);
-        assert_eq!(buffer.buffer.len(), 2);
+        assert_eq!(buffer.pending_bytes(), 2);
     }
 }
@@ -0,0 +1,44 @@
+//! Policies configuring how the `RollingStats` treats specific situations of the data ingestion.
+
+/// Denotes how the non-finite (NaN or infinite) values parsed from the raw data are treated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonFinitePolicy {
+    /// The values are pushed to the window, making the statistics non-finite as well.
+    #[default]
+    Propagate,
+    /// The values are skipped and counted as such.
+    Skip,
+    /// The values are rejected, the `write` method returns an `InvalidData` error.
+    Error,
+}
+
+/// Denotes what happens to new values once the window is full.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EvictionPolicy {
+    /// The oldest values are evicted from the window to make room for the new ones.
+    #[default]
+    Evict,
+    /// The new values are rejected, the `write` method consumes no more raw data until the window is cleared.
+    RejectWhenFull,
+}
+
+/// Denotes how the incomplete raw data, split between consecutive writes, are reconstructed.
+/// Both of the strategies produce the same values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReconstructionStrategy {
+    /// The incomplete data are completed in a small buffer and the rest of the raw data are parsed in place, avoiding copies.
+    PartialDataBuffer,
+    /// The raw data are parsed into an intermediate buffer of values using the `Reconstructor`, which involves one more copy.
+    Reconstructor,
+}
+
+impl Default for ReconstructionStrategy {
+    /// Returns the `Reconstructor` strategy if the `reconstructor` feature is enabled, the `PartialDataBuffer` strategy otherwise.
+    fn default() -> Self {
+        if cfg!(feature = "reconstructor") {
+            Self::Reconstructor
+        } else {
+            Self::PartialDataBuffer
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! Exposition of the statistics of the windows of a registry in the Prometheus text format, served to the Prometheus scraper as is.
+
+use crate::{Registry, Report};
+use alloc::{string::String, vec::Vec};
+use core::fmt::{Display, Write};
+use rand_distr::num_traits::Float;
+
+/// The gauges exposed for each window - the name and the help of the gauge.
+const GAUGES: [(&str, &str); 5] = [
+    ("mean", "The mean of the window."),
+    ("std_dev", "The standard deviation of the window."),
+    ("min", "The smallest item of the window."),
+    ("max", "The largest item of the window."),
+    ("count", "The number of items in the window."),
+];
+
+impl<O> Registry<O>
+where
+    O: Float + Display,
+{
+    /// Writes the mean, the standard deviation, the minimum, the maximum and the number of items of each of the windows as gauges
+    /// in the Prometheus text exposition format, such as `rolling_stats_mean{metric="motor_current"} 4`.
+    /// The gauge names are prefixed by the `prefix` followed by an underscore, the name of the window is the `metric` label.
+    /// The characters of the prefix not allowed in the metric names are replaced by underscores.
+    /// The statistics of an empty window, except for the count, are `NaN`, so that they are not mistaken for zeros.
+    pub fn encode_prometheus<W: Write>(&self, prefix: &str, w: &mut W) -> core::fmt::Result {
+        let reports: Vec<(&str, Report<O>)> = self.reports().collect();
+        let prefix = sanitize_prefix(prefix);
+
+        for (index, (gauge, help)) in GAUGES.iter().enumerate() {
+            writeln!(w, "# HELP {}_{} {}", prefix, gauge, help)?;
+            writeln!(w, "# TYPE {}_{} gauge", prefix, gauge)?;
+            for (name, report) in reports.iter() {
+                let value = match index {
+                    _ if index < 4 && report.len == 0 => O::nan(),
+                    0 => report.mean,
+                    1 => report.std_dev,
+                    2 => report.min,
+                    3 => report.max,
+                    _ => O::from(report.len).unwrap_or_else(O::nan),
+                };
+
+                write!(w, "{}_{}{{metric=\"", prefix, gauge)?;
+                write_label_value(w, name)?;
+                write!(w, "\"}} ")?;
+                write_value(w, value)?;
+                writeln!(w)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the prefix matching `[a-zA-Z_:][a-zA-Z0-9_:]*`, the disallowed characters replaced by underscores
+/// and an underscore prepended to a leading digit.
+fn sanitize_prefix(prefix: &str) -> String {
+    let mut sanitized = String::with_capacity(prefix.len() + 1);
+    if prefix.starts_with(|c: char| c.is_ascii_digit()) {
+        sanitized.push('_');
+    }
+    sanitized.extend(prefix.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '_' || c == ':' {
+            c
+        } else {
+            '_'
+        }
+    }));
+
+    sanitized
+}
+
+/// Writes the label value, escaping the backslashes, the double quotes and the line feeds.
+fn write_label_value<W: Write>(w: &mut W, value: &str) -> core::fmt::Result {
+    for c in value.chars() {
+        match c {
+            '\\' => w.write_str("\\\\")?,
+            '"' => w.write_str("\\\"")?,
+            '\n' => w.write_str("\\n")?,
+            c => w.write_char(c)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the sample value, the non-finite values are written as `NaN`, `+Inf` and `-Inf`.
+fn write_value<W: Write, O: Float + Display>(w: &mut W, value: O) -> core::fmt::Result {
+    if value.is_nan() {
+        w.write_str("NaN")
+    } else if value.is_infinite() {
+        w.write_str(if value > O::zero() { "+Inf" } else { "-Inf" })
+    } else {
+        write!(w, "{}", value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, Registry, RollingStats};
+    use alloc::{string::String, vec::Vec};
+
+    #[test]
+    fn exposition() {
+        let mut registry = Registry::new();
+        registry.register("current", RollingStats::<i32, BigEndian, 2>::new());
+        registry.register("a \"b\"", RollingStats::<i32, BigEndian, 2>::new());
+        registry
+            .write("current", &[0, 0, 0, 1, 0, 0, 0, 3])
+            .unwrap();
+
+        let mut text = String::new();
+        registry.encode_prometheus("gateway", &mut text).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 5 * 4);
+        assert_eq!(
+            &lines[..4],
+            &[
+                "# HELP gateway_mean The mean of the window.",
+                "# TYPE gateway_mean gauge",
+                "gateway_mean{metric=\"a \\\"b\\\"\"} NaN",
+                "gateway_mean{metric=\"current\"} 2",
+            ]
+        );
+        assert!(lines.contains(&"gateway_std_dev{metric=\"current\"} 1.4142135"));
+        assert!(lines.contains(&"gateway_count{metric=\"current\"} 2"));
+        assert!(lines.contains(&"gateway_min{metric=\"a \\\"b\\\"\"} NaN"));
+        assert!(lines.contains(&"gateway_count{metric=\"a \\\"b\\\"\"} 0"));
+    }
+
+    #[test]
+    fn sanitized_prefix() {
+        assert_eq!(super::sanitize_prefix("edge:gateway_1"), "edge:gateway_1");
+        assert_eq!(super::sanitize_prefix("edge-gateway 1"), "edge_gateway_1");
+        assert_eq!(super::sanitize_prefix("1st"), "_1st");
+
+        let mut registry = Registry::new();
+        registry.register("current", RollingStats::<i32, BigEndian, 2>::new());
+        let mut text = String::new();
+        registry
+            .encode_prometheus("edge.gateway", &mut text)
+            .unwrap();
+        assert!(text.starts_with("# HELP edge_gateway_mean "));
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! The abstraction is meant to be implemented by various types denoting raw data conversion - such as in this case types denoting big and little endian number representations.
 
-use core::convert::TryInto;
+use alloc::{vec, vec::Vec};
+use core::{convert::TryInto, marker::PhantomData};
+use rand_distr::num_traits::Float;
+#[cfg(feature = "std")]
 use thiserror::Error;
 
 /// The LittleEndian struct represents raw bytes conversion technique based on the Little Endian memory layout.
@@ -25,6 +28,50 @@ pub struct LittleEndian;
 /// ```
 pub struct BigEndian;
 
+/// The NativeEndian struct represents raw bytes conversion technique based on the memory layout of the target platform.
+/// # Examples
+/// ```
+/// use rolling_stats::{NativeEndian, ConverterFromRaw};
+///
+/// let raw_data = 1.5f32.to_ne_bytes();
+/// assert_eq!(1.5f32, NativeEndian::from_raw(&raw_data).unwrap());
+/// ```
+pub struct NativeEndian;
+
+/// The RuntimeWidth struct represents conversion of signed integers, whose width is known only at runtime, into `i64`.
+/// The width of the integer is given by the length of the raw bytes (1 to 8 bytes), which are interpreted using the `E` byte order and sign-extended.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, ConverterFromRaw, RuntimeWidth};
+///
+/// let raw_data = [0xffu8, 0xfe];
+/// assert_eq!(-2i64, RuntimeWidth::<BigEndian>::from_raw(&raw_data).unwrap());
+/// ```
+pub struct RuntimeWidth<E>(PhantomData<E>);
+
+/// The CanonicalNan struct wraps a float conversion technique `E`, replacing any decoded NaN with the single canonical NaN representation.
+/// Producers of the raw data may emit different NaN bit patterns, the plain converters keep the bit patterns untouched.
+/// # Examples
+/// ```
+/// use rolling_stats::{CanonicalNan, ConverterFromRaw, LittleEndian};
+///
+/// let raw_data = [1u8, 0, 0xc0, 0x7f];
+/// let value: f32 = CanonicalNan::<LittleEndian>::from_raw(&raw_data).unwrap();
+/// assert_eq!(value.to_bits(), f32::NAN.to_bits());
+/// ```
+pub struct CanonicalNan<E>(PhantomData<E>);
+
+/// The RejectNan struct wraps a float conversion technique `E`, rejecting any decoded NaN with the `RawConversionError::NotANumber` error.
+/// # Examples
+/// ```
+/// use rolling_stats::{ConverterFromRaw, LittleEndian, RejectNan};
+///
+/// let raw_data = f32::NAN.to_le_bytes();
+/// let value: Result<f32, _> = RejectNan::<LittleEndian>::from_raw(&raw_data);
+/// assert!(value.is_err());
+/// ```
+pub struct RejectNan<E>(PhantomData<E>);
+
 /// Trait utilized for implementing conversion of raw bytes into specific types.
 /// Implemented by Converter structs such as the `LittleEndian` and `BigEndian` structs.
 /// `T` denotes the type the raw bytes should be converted into.
@@ -35,29 +82,168 @@ pub trait ConverterFromRaw<T> {
     fn from_raw(raw: &[u8]) -> Result<T, RawConversionError>;
 }
 
-/// An Error returned by the `ConverterFromRaw` trait on conversion failure.
-#[derive(Clone, Copy, Debug, Error)]
+/// Trait utilized for implementing conversion of specific types into raw bytes, the counterpart of `ConverterFromRaw`.
+/// Implemented by Converter structs such as the `LittleEndian` and `BigEndian` structs.
+/// `T` denotes the type to be converted into the raw bytes.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, ConverterToRaw};
+///
+/// assert_eq!(BigEndian::encode(1i32).unwrap(), vec![0, 0, 0, 1]);
+/// assert_eq!(BigEndian::encode_slice(&[1i32, 2]).unwrap(), vec![0, 0, 0, 1, 0, 0, 0, 2]);
+/// ```
+pub trait ConverterToRaw<T> {
+    /// Writes the raw representation of the value into the raw buffer or returns an error.
+    /// # Arguments
+    /// * `value` - the value to be converted.
+    /// * `raw` - raw bytes the value will be written to, length should be the same or longer than the type itself.
+    fn to_raw(value: T, raw: &mut [u8]) -> Result<(), RawConversionError>;
+
+    /// Returns the raw representation of the value, as long as the type itself, or an error.
+    fn encode(value: T) -> Result<Vec<u8>, RawConversionError> {
+        let mut raw = vec![0; core::mem::size_of::<T>()];
+        Self::to_raw(value, &mut raw)?;
+        Ok(raw)
+    }
+
+    /// Returns the concatenated raw representations of the values or an error.
+    fn encode_slice(values: &[T]) -> Result<Vec<u8>, RawConversionError>
+    where
+        T: Copy,
+    {
+        Self::encode_iter(values.iter().copied())
+    }
+
+    /// Returns the concatenated raw representations of the values produced by the iterator or an error.
+    fn encode_iter<I>(values: I) -> Result<Vec<u8>, RawConversionError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut raw = Vec::new();
+        for value in values {
+            let start = raw.len();
+            raw.resize(start + core::mem::size_of::<T>(), 0);
+            Self::to_raw(value, &mut raw[start..])?;
+        }
+        Ok(raw)
+    }
+}
+
+/// An Error returned by the `ConverterFromRaw` and `ConverterToRaw` traits on conversion failure.
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "std", derive(Error))]
 pub enum RawConversionError {
-    #[error("Not enough raw bytes were available for type conversion.")]
+    #[cfg_attr(
+        feature = "std",
+        error("Not enough raw bytes were available for type conversion.")
+    )]
     NotEnoughData,
+    #[cfg_attr(
+        feature = "std",
+        error("The raw bytes represent a NaN, which is not accepted.")
+    )]
+    NotANumber,
 }
 
-impl ConverterFromRaw<i32> for LittleEndian {
-    fn from_raw(raw: &[u8]) -> Result<i32, RawConversionError> {
-        if raw.len() < std::mem::size_of::<i32>() {
+impl ConverterFromRaw<i64> for RuntimeWidth<LittleEndian> {
+    fn from_raw(raw: &[u8]) -> Result<i64, RawConversionError> {
+        if raw.is_empty() || raw.len() > core::mem::size_of::<i64>() {
             return Err(RawConversionError::NotEnoughData);
         }
 
-        Ok(i32::from_le_bytes(raw[..4].try_into().unwrap()))
+        let mut bytes = [0u8; 8];
+        bytes[8 - raw.len()..].copy_from_slice(raw);
+        bytes[8 - raw.len()..].reverse();
+        Ok(i64::from_be_bytes(bytes) << (64 - 8 * raw.len()) >> (64 - 8 * raw.len()))
     }
 }
 
-impl ConverterFromRaw<i32> for BigEndian {
-    fn from_raw(raw: &[u8]) -> Result<i32, RawConversionError> {
-        if raw.len() < std::mem::size_of::<i32>() {
+impl ConverterFromRaw<i64> for RuntimeWidth<BigEndian> {
+    fn from_raw(raw: &[u8]) -> Result<i64, RawConversionError> {
+        if raw.is_empty() || raw.len() > core::mem::size_of::<i64>() {
             return Err(RawConversionError::NotEnoughData);
         }
 
-        Ok(i32::from_be_bytes(raw[..4].try_into().unwrap()))
+        let mut bytes = [0u8; 8];
+        bytes[8 - raw.len()..].copy_from_slice(raw);
+        Ok(i64::from_be_bytes(bytes) << (64 - 8 * raw.len()) >> (64 - 8 * raw.len()))
+    }
+}
+
+/// Implements the conversions of number types from and to raw bytes for a byte order converter.
+macro_rules! impl_raw_conversions {
+    ($converter:ty, $from_bytes:ident, $to_bytes:ident, $($t:ty),*) => {
+        $(
+            impl ConverterFromRaw<$t> for $converter {
+                fn from_raw(raw: &[u8]) -> Result<$t, RawConversionError> {
+                    const SIZE: usize = core::mem::size_of::<$t>();
+                    if raw.len() < SIZE {
+                        return Err(RawConversionError::NotEnoughData);
+                    }
+
+                    Ok(<$t>::$from_bytes(raw[..SIZE].try_into().unwrap()))
+                }
+            }
+
+            impl ConverterToRaw<$t> for $converter {
+                fn to_raw(value: $t, raw: &mut [u8]) -> Result<(), RawConversionError> {
+                    const SIZE: usize = core::mem::size_of::<$t>();
+                    if raw.len() < SIZE {
+                        return Err(RawConversionError::NotEnoughData);
+                    }
+
+                    raw[..SIZE].copy_from_slice(&value.$to_bytes());
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_raw_conversions!(LittleEndian, from_le_bytes, to_le_bytes, i32, f32, f64);
+impl_raw_conversions!(BigEndian, from_be_bytes, to_be_bytes, i32, f32, f64);
+impl_raw_conversions!(NativeEndian, from_ne_bytes, to_ne_bytes, i32, f32, f64);
+
+impl<F, E> ConverterFromRaw<F> for CanonicalNan<E>
+where
+    F: Float,
+    E: ConverterFromRaw<F>,
+{
+    fn from_raw(raw: &[u8]) -> Result<F, RawConversionError> {
+        let value = E::from_raw(raw)?;
+        Ok(if value.is_nan() { F::nan() } else { value })
+    }
+}
+
+impl<F, E> ConverterToRaw<F> for CanonicalNan<E>
+where
+    E: ConverterToRaw<F>,
+{
+    fn to_raw(value: F, raw: &mut [u8]) -> Result<(), RawConversionError> {
+        E::to_raw(value, raw)
+    }
+}
+
+impl<F, E> ConverterFromRaw<F> for RejectNan<E>
+where
+    F: Float,
+    E: ConverterFromRaw<F>,
+{
+    fn from_raw(raw: &[u8]) -> Result<F, RawConversionError> {
+        let value = E::from_raw(raw)?;
+        if value.is_nan() {
+            return Err(RawConversionError::NotANumber);
+        }
+
+        Ok(value)
+    }
+}
+
+impl<F, E> ConverterToRaw<F> for RejectNan<E>
+where
+    E: ConverterToRaw<F>,
+{
+    fn to_raw(value: F, raw: &mut [u8]) -> Result<(), RawConversionError> {
+        E::to_raw(value, raw)
     }
 }
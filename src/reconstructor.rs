@@ -1,36 +1,70 @@
 //! Reconstructor serves as a raw data stream parser that deals with interrupted/incomplete data.
 //! That means raw data streams which do not contain whole multiplies of the Type length.
+//! It can be used on its own to turn a stream of raw data into typed values, without the statistics window.
 
-use crate::ConverterFromRaw;
-use std::{io::ErrorKind, marker::PhantomData};
+use crate::{ConverterFromRaw, Error};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
 /// Reconstructor is a structure that holds all of the intermediate buffers
 /// when receiving data using the `std::io::Write` trait.
 /// It depends on the raw converter represented by the type parameter `E`.
 /// The output type is denoted T.
 /// The intermediate result (parsed `T`s) are contained in the Reconstructor itself
-/// and are than retrieved using an iterator and cleared via the `std::io::Write` `flush` method.
+/// and are than retrieved using an iterator, removed using the `drain` method, or taken out using the `take_data` method.
+///
+/// # Example
+/// ```
+/// use rolling_stats::{LittleEndian, Reconstructor};
+///
+/// let mut reconstructor = Reconstructor::<i32, LittleEndian>::default();
+/// reconstructor.feed(&[1, 0, 0, 0, 2, 0]).unwrap();
+/// assert_eq!(reconstructor.raw_data(), &[1]);
+/// assert_eq!(reconstructor.pending_bytes(), 2);
+///
+/// reconstructor.feed(&[0, 0, 3, 0, 0, 0]).unwrap();
+/// assert_eq!(reconstructor.drain().collect::<Vec<_>>(), vec![1, 2, 3]);
+/// assert!(reconstructor.raw_data().is_empty());
+/// ```
 pub struct Reconstructor<T, E> {
     _e: PhantomData<E>,
     /// A buffer that stores leftower raw data.
     intermediate_buffer: Vec<u8>,
     /// A buffer that stores the parsed data.
     buffer: Vec<T>,
+    /// Size of the raw representation of the parsed type.
+    type_size: usize,
 }
 
 /// Creates an empty Reconstructor instance with both of the intermediate buffers empty.
 impl<T, E> Default for Reconstructor<T, E> {
     fn default() -> Self {
+        Self::with_type_size(core::mem::size_of::<T>())
+    }
+}
+
+impl<T: Clone, E> Clone for Reconstructor<T, E> {
+    fn clone(&self) -> Self {
         Self {
             _e: PhantomData,
-            intermediate_buffer: Vec::new(),
-            buffer: Vec::new(),
+            intermediate_buffer: self.intermediate_buffer.clone(),
+            buffer: self.buffer.clone(),
+            type_size: self.type_size,
         }
     }
 }
 
-#[allow(unused)]
 impl<T, E> Reconstructor<T, E> {
+    /// Creates an empty Reconstructor parsing items `type_size` bytes long, regardless of the size of `T`.
+    pub fn with_type_size(type_size: usize) -> Self {
+        Self {
+            _e: PhantomData,
+            intermediate_buffer: Vec::new(),
+            buffer: Vec::new(),
+            type_size,
+        }
+    }
+
     /// Returns an iterator over references to the parsed data.
     pub fn data(&self) -> impl Iterator<Item = &T> {
         self.buffer.iter()
@@ -40,22 +74,45 @@ impl<T, E> Reconstructor<T, E> {
     pub fn raw_data(&self) -> &[T] {
         &self.buffer
     }
+
+    /// Returns the number of leftover raw bytes waiting for the rest of the data.
+    pub fn pending_bytes(&self) -> usize {
+        self.intermediate_buffer.len()
+    }
+
+    /// Returns the size of the raw representation of the parsed type.
+    pub fn type_size(&self) -> usize {
+        self.type_size
+    }
+
+    /// Returns an iterator removing the parsed data from the data buffer, the oldest first.
+    /// The data not yet yielded are removed once the iterator is dropped.
+    pub fn drain(&mut self) -> alloc::vec::Drain<'_, T> {
+        self.buffer.drain(..)
+    }
+
+    /// Takes the parsed data out of the data buffer, leaving it empty, so that the following data are parsed into a new buffer.
+    pub fn take_data(&mut self) -> Vec<T> {
+        core::mem::take(&mut self.buffer)
+    }
+
+    /// Discards the leftover raw bytes.
+    pub fn discard_pending(&mut self) {
+        self.intermediate_buffer.clear();
+    }
 }
 
-/// The trait `std::io::Write` represents the data input into the RollingStats structure (the Reconstructor).
-/// The raw data are parsed using the specified `ConverterFromRaw<T>` and stored into a buffer.
-/// In the case of any leftover data, these are stored into the intermediate buffer, where they are retrieved once new raw data is written.
-/// The contents of the parsed data buffer can be cleared using the `flush` method.
-impl<T, E> std::io::Write for Reconstructor<T, E>
+impl<T, E> Reconstructor<T, E>
 where
     E: ConverterFromRaw<T>,
 {
     /// Parses the raw data into the concrete types and stores them into the data buffer.
+    /// The counterpart of the `std::io::Write::write` method, available without the `std` feature.
     /// # Returns
     /// Returns the number of processed raw bytes (should always be equel to the length of the input raw data),
     /// or returns an error from parsing the raw data.
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        let type_size = std::mem::size_of::<T>();
+    pub fn feed(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let type_size = self.type_size;
         if (buf.len() + self.intermediate_buffer.len()) < type_size {
             self.intermediate_buffer.extend_from_slice(buf);
             return Ok(buf.len());
@@ -72,8 +129,9 @@ where
             data.extend_from_slice(&self.intermediate_buffer);
             data.extend_from_slice(&buf[..offset]);
 
-            self.buffer.push(E::from_raw(&data).unwrap());
             self.intermediate_buffer.clear();
+            let value = E::from_raw(&data)?;
+            self.buffer.push(value);
         }
 
         let chunks = buf[offset..].chunks_exact(type_size);
@@ -82,25 +140,52 @@ where
             .extend_from_slice(chunks.remainder());
 
         for value in chunks.map(|c| E::from_raw(c)) {
-            let value = value.map_err(|_| {
-                std::io::Error::new(ErrorKind::InvalidData, "Data conversion failed.")
-            })?;
+            let value = value?;
             self.buffer.push(value)
         }
 
         Ok(buf.len())
     }
+}
 
-    /// Clears the data buffer.
+/// The trait `std::io::Write` represents the data input into the RollingStats structure (the Reconstructor).
+/// The raw data are parsed using the specified `ConverterFromRaw<T>` and stored into a buffer.
+/// In the case of any leftover data, these are stored into the intermediate buffer, where they are retrieved once new raw data is written.
+/// The parsed data are kept until removed using the `drain` or `take_data` method, flushing doesn't affect them.
+#[cfg(feature = "std")]
+impl<T, E> std::io::Write for Reconstructor<T, E>
+where
+    E: ConverterFromRaw<T>,
+{
+    /// Parses the raw data into the concrete types and stores them into the data buffer.
     /// # Returns
-    /// Doesn't return any error as there are no fallible operations.
+    /// Returns the number of processed raw bytes (should always be equel to the length of the input raw data),
+    /// or returns an `InvalidData` error from parsing the raw data.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.feed(buf)?)
+    }
+
+    /// Parses the raw data of all of the slices as if they were written at once, incomplete data may span the slice boundaries.
+    /// # Returns
+    /// Returns the number of processed raw bytes (should always be equal to the total length of the slices),
+    /// or returns an error from parsing the raw data.
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        let mut written = 0;
+        for buf in bufs {
+            written += self.write(buf)?;
+        }
+
+        Ok(written)
+    }
+
+    /// Does nothing, as the data are parsed as soon as they are written.
+    /// The parsed data are kept until removed using the `drain` or `take_data` method.
     fn flush(&mut self) -> std::io::Result<()> {
-        self.buffer.clear();
         Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use crate::raw::BigEndian;
     use std::io::Write;
@@ -117,6 +202,35 @@ mod tests {
         assert_eq!(reconstructor.raw_data(), &[1, 2, 3, 4]);
     }
 
+    #[test]
+    fn drain_keeps_data_on_flush() {
+        let mut reconstructor = Reconstructor::<i32, BigEndian>::default();
+        reconstructor.write_all(&[0, 0, 0, 1, 0, 0, 0, 2]).unwrap();
+        reconstructor.flush().unwrap();
+        assert_eq!(reconstructor.raw_data(), &[1, 2]);
+
+        let mut drain = reconstructor.drain();
+        assert_eq!(drain.next(), Some(1));
+        drop(drain);
+        assert!(reconstructor.raw_data().is_empty());
+
+        reconstructor.write_all(&[0, 0, 0, 3]).unwrap();
+        assert_eq!(reconstructor.drain().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn vectored() {
+        let mut reconstructor = Reconstructor::<i32, BigEndian>::default();
+        let bufs = [
+            std::io::IoSlice::new(&[0, 0, 0]),
+            std::io::IoSlice::new(&[1, 0, 0, 0, 2, 0]),
+            std::io::IoSlice::new(&[0, 0, 3]),
+        ];
+        assert_eq!(reconstructor.write_vectored(&bufs).unwrap(), 12);
+
+        assert_eq!(reconstructor.raw_data(), &[1, 2, 3]);
+    }
+
     #[test]
     fn partial_data_leading() {
         let mut reconstructor = Reconstructor::<i32, BigEndian>::default();
@@ -132,6 +246,7 @@ mod tests {
     fn partial_data_trailing() {
         let mut reconstructor = Reconstructor::<i32, BigEndian>::default();
         let _ = reconstructor.write(&[0, 0, 0, 1, 0, 0]).unwrap();
+        assert_eq!(reconstructor.pending_bytes(), 2);
         let _ = reconstructor
             .write(&[0, 2, 0, 0, 0, 3, 0, 0, 0, 4])
             .unwrap();
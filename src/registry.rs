@@ -0,0 +1,213 @@
+//! A registry of named windows, such as the metrics of a gateway, each of them with its own item type, converter and size.
+
+use crate::{
+    convertfloat::LossyFloatConvertible, ConverterFromRaw, Error, Report, RollingStats,
+    WindowStorage,
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+use core::ops::Add;
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+
+/// A window fed by the raw data and reporting its statistics, regardless of its item type, converter and size.
+/// Implemented for all of the `RollingStats`, so that windows of different types can be stored in the `Registry`.
+pub trait Metric<O = f32> {
+    /// Parses the raw data into the window, see `RollingStats::feed`.
+    fn feed(&mut self, buf: &[u8]) -> Result<usize, Error>;
+
+    /// Returns a report of the current statistics of the window.
+    fn report(&self) -> Report<O>;
+
+    /// Empties the window, see `RollingStats::clear`.
+    fn clear(&mut self);
+
+    /// Discards the incomplete raw data waiting for the rest of the data, such as at the end of a message.
+    /// # Returns
+    /// Returns the number of the discarded bytes, see `RollingStats::pending_bytes`.
+    fn discard_pending(&mut self) -> usize;
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> Metric<O> for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    E: ConverterFromRaw<T>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    fn feed(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        RollingStats::feed(self, buf)
+    }
+
+    fn report(&self) -> Report<O> {
+        RollingStats::report(self)
+    }
+
+    fn clear(&mut self) {
+        RollingStats::clear(self)
+    }
+
+    fn discard_pending(&mut self) -> usize {
+        let pending = self.pending_bytes();
+        RollingStats::discard_pending(self);
+        pending
+    }
+}
+
+/// Named windows of possibly different types, all of them computing the statistics in the float type `O`.
+/// The windows are iterated in the order of their names.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, LittleEndian, Registry, RollingStats};
+///
+/// let mut registry = Registry::new();
+/// registry.register("motor_current", RollingStats::<i32, BigEndian, 10>::new());
+/// registry.register("temperature", RollingStats::<f32, LittleEndian, 60>::new());
+///
+/// registry.write("motor_current", &[0, 0, 0, 3, 0, 0, 0, 5]).unwrap();
+/// registry.write("temperature", &21.5f32.to_le_bytes()).unwrap();
+/// assert!(registry.write("voltage", &[0, 1]).is_err());
+///
+/// for (name, report) in registry.reports() {
+///     println!("{}: {}", name, report.mean);
+/// }
+/// assert_eq!(registry.report("motor_current").unwrap().mean, 4.0);
+/// ```
+pub struct Registry<O = f32> {
+    metrics: BTreeMap<String, Box<dyn Metric<O> + Send>>,
+}
+
+impl<O> Registry<O> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self {
+            metrics: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the window under the name.
+    /// The windows have to be `Send`, so that the registry can be moved to the thread serving the statistics.
+    /// # Returns
+    /// Returns the window previously registered under the name, if any.
+    pub fn register<M>(&mut self, name: &str, metric: M) -> Option<Box<dyn Metric<O> + Send>>
+    where
+        M: Metric<O> + Send + 'static,
+    {
+        self.metrics.insert(name.to_string(), Box::new(metric))
+    }
+
+    /// Removes the window registered under the name.
+    pub fn unregister(&mut self, name: &str) -> Option<Box<dyn Metric<O> + Send>> {
+        self.metrics.remove(name)
+    }
+
+    /// Returns the window registered under the name.
+    pub fn get(&self, name: &str) -> Option<&(dyn Metric<O> + Send)> {
+        self.metrics.get(name).map(|metric| &**metric)
+    }
+
+    /// Returns the window registered under the name mutably.
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut (dyn Metric<O> + Send + 'static)> {
+        self.metrics.get_mut(name).map(|metric| &mut **metric)
+    }
+
+    /// Returns the names of the registered windows, in order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.metrics.keys().map(String::as_str)
+    }
+
+    /// Returns the number of the registered windows.
+    pub fn len(&self) -> usize {
+        self.metrics.len()
+    }
+
+    /// Returns `true` if no window is registered.
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty()
+    }
+
+    /// Parses the raw data into the window registered under the name, see `RollingStats::feed`.
+    /// # Errors
+    /// Returns the `UnknownMetric` error if no window is registered under the name, or the error of the window.
+    pub fn write(&mut self, name: &str, buf: &[u8]) -> Result<usize, Error> {
+        self.metrics
+            .get_mut(name)
+            .ok_or_else(|| Error::UnknownMetric(name.to_string()))?
+            .feed(buf)
+    }
+
+    /// Returns the report of the window registered under the name.
+    pub fn report(&self, name: &str) -> Option<Report<O>> {
+        self.metrics.get(name).map(|metric| metric.report())
+    }
+
+    /// Returns the pairs of the name and the report of each of the windows, in the order of the names.
+    pub fn reports(&self) -> impl Iterator<Item = (&str, Report<O>)> {
+        self.metrics
+            .iter()
+            .map(|(name, metric)| (name.as_str(), metric.report()))
+    }
+
+    /// Empties all of the windows.
+    pub fn clear(&mut self) {
+        self.metrics.values_mut().for_each(|metric| metric.clear());
+    }
+}
+
+impl<O> Default for Registry<O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{BigEndian, LittleEndian};
+
+    #[test]
+    fn moves_between_threads() {
+        let mut registry = Registry::<f32>::new();
+        registry.register("a", RollingStats::<i32, BigEndian, 2>::new());
+        registry.write("a", &[0, 0, 0, 4]).unwrap();
+
+        let mean = std::thread::spawn(move || registry.report("a").unwrap().mean)
+            .join()
+            .unwrap();
+        assert_eq!(mean, 4.0);
+    }
+
+    #[test]
+    fn heterogeneous_metrics() {
+        let mut registry = Registry::<f64>::new();
+        registry.register("b", RollingStats::<i32, BigEndian, 2, f64>::new());
+        registry.register("a", RollingStats::<f64, LittleEndian, 3, f64>::new());
+
+        assert_eq!(registry.write("b", &[0, 0, 0, 1, 0, 0]).unwrap(), 6);
+        registry.write("b", &[0, 3]).unwrap();
+        registry.write("a", &2.5f64.to_le_bytes()).unwrap();
+        assert!(matches!(
+            registry.write("c", &[0]),
+            Err(Error::UnknownMetric(name)) if name == "c"
+        ));
+
+        let means: Vec<(&str, f64)> = registry
+            .reports()
+            .map(|(name, report)| (name, report.mean))
+            .collect();
+        assert_eq!(means, vec![("a", 2.5), ("b", 2.0)]);
+
+        assert!(registry
+            .register("a", RollingStats::<i32, BigEndian, 1, f64>::new())
+            .is_some());
+        assert_eq!(registry.report("a").unwrap().len, 0);
+
+        registry.clear();
+        assert_eq!(registry.get("b").unwrap().report().len, 0);
+        assert!(registry.unregister("b").is_some());
+        assert_eq!(registry.names().collect::<Vec<_>>(), vec!["a"]);
+    }
+}
@@ -0,0 +1,339 @@
+//! Report is a snapshot of the significant statistics of the window.
+//!
+//! The reports can also be recorded after each pushed value, forming a trajectory of how the statistics evolved.
+
+use crate::{
+    convertfloat::LossyFloatConvertible, DynRollingStats, RollingStats, Statistics, WindowStorage,
+};
+use alloc::vec::Vec;
+use core::{
+    fmt::{Display, Formatter},
+    ops::Add,
+};
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+
+/// A snapshot of the significant statistics of the window.
+/// `O` denotes the float type the statistics are computed in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Report<O = f32> {
+    /// The number of items in the window.
+    pub len: usize,
+    /// The mean of the window.
+    pub mean: O,
+    /// The standard deviation of the window.
+    pub std_dev: O,
+    /// The smallest item of the window, zero for an empty window.
+    pub min: O,
+    /// The largest item of the window, zero for an empty window.
+    pub max: O,
+}
+
+/// The differences between the statistics of two windows, such as of a live window and of a baseline, returned by the `compare` method.
+/// Every difference is the value of the compared window minus the value of the other window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatsDelta<O = f32> {
+    /// The difference of the numbers of items.
+    pub len: isize,
+    /// The difference of the means.
+    pub mean: O,
+    /// The difference of the standard deviations.
+    pub std_dev: O,
+}
+
+impl<O: Float> Report<O> {
+    /// Returns the differences between the statistics of this report and of the other report, such as a baseline.
+    pub fn compare(&self, other: &Report<O>) -> StatsDelta<O> {
+        StatsDelta {
+            len: self.len as isize - other.len as isize,
+            mean: self.mean - other.mean,
+            std_dev: self.std_dev - other.std_dev,
+        }
+    }
+}
+
+/// Records the reports of the last `len` pushed values.
+/// The reports are stored in a buffer of up to twice the length, which is trimmed once full,
+/// so that the recorded reports are always available as a single slice.
+pub(crate) struct Trajectory<S, O> {
+    len: usize,
+    reports: Vec<Report<O>>,
+    /// Creates the report of the window, captured when enabling the trajectory, so that pushing values doesn't require the statistics bounds.
+    report: fn(&S) -> Report<O>,
+}
+
+impl<S, O: Clone> Clone for Trajectory<S, O> {
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len,
+            reports: self.reports.clone(),
+            report: self.report,
+        }
+    }
+}
+
+impl<S, O> Trajectory<S, O> {
+    fn record(&mut self, report: Report<O>) {
+        if self.len == 0 {
+            return;
+        }
+        if self.reports.len() >= 2 * self.len {
+            self.reports.drain(..self.reports.len() - self.len + 1);
+        }
+        self.reports.push(report);
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.reports.clear();
+    }
+
+    fn reports(&self) -> &[Report<O>] {
+        &self.reports[self.reports.len().saturating_sub(self.len)..]
+    }
+}
+
+/// Creates the report of the values the same way the `report` method of a window holding them does,
+/// including the time decay of the window, the newest of the values having the weight of one.
+pub(crate) fn summarize<T, O, I>(values: I, ddof: usize, decay: Option<O>) -> Report<O>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    I: IntoIterator<Item = T>,
+{
+    let mut window = DynRollingStats::<T, (), O>::new().with_ddof(ddof);
+    window.decay = decay;
+    window.buffer.extend(values);
+    window.window_size = window.buffer.len();
+    window.report()
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    /// Returns the reports of every sub-window of `len` consecutive items of the window, the oldest first, such as to find the quietest segment of the window.
+    /// The sub-windows overlap the same way the `slice::windows` do, no report is returned if the window holds less than `len` items.
+    /// With the time decay enabled, the items of each sub-window are weighted by their age within the sub-window.
+    /// # Panics
+    /// Panics if `len` is zero.
+    pub fn windows(&self, len: usize) -> impl Iterator<Item = Report<O>> + '_ {
+        assert!(len != 0, "window size must be non-zero");
+
+        let (ddof, decay) = (self.ddof, self.decay);
+        (0..(self.len() + 1).saturating_sub(len)).map(move |start| {
+            summarize(
+                self.buffer.iter().skip(start).take(len).copied(),
+                ddof,
+                decay,
+            )
+        })
+    }
+
+    /// Returns a report of the current statistics of the window.
+    pub fn report(&self) -> Report<O> {
+        let min = self
+            .buffer
+            .iter()
+            .copied()
+            .reduce(|a, b| if b < a { b } else { a });
+        let max = self
+            .buffer
+            .iter()
+            .copied()
+            .reduce(|a, b| if b > a { b } else { a });
+
+        Report {
+            len: self.len(),
+            mean: self.mean(),
+            std_dev: self.std_dev(),
+            min: min.map_or(O::zero(), |min| min.convert()),
+            max: max.map_or(O::zero(), |max| max.convert()),
+        }
+    }
+
+    /// Returns the differences between the statistics of this window and of the other window, such as a baseline.
+    /// The other window may hold items of a different type or have a different size, only the float type of the statistics has to match.
+    pub fn compare<U, F, const OTHER_SIZE: usize, R>(
+        &self,
+        other: &RollingStats<U, F, OTHER_SIZE, O, R>,
+    ) -> StatsDelta<O>
+    where
+        U: Copy + Default + PartialOrd + Add<U, Output = U> + LossyFloatConvertible<O>,
+        R: WindowStorage<U>,
+    {
+        self.report().compare(&other.report())
+    }
+
+    /// Writes a compact summary of the statistics of the window, such as `n=3 mean=2 sd=1 min=1 max=3`, into a `core::fmt::Write` sink.
+    /// Meant for logging, where the `std::io::Write` is not available.
+    pub fn fmt_summary<W: core::fmt::Write>(&self, w: &mut W) -> core::fmt::Result
+    where
+        O: Display,
+    {
+        let report = self.report();
+        write!(
+            w,
+            "n={} mean={} sd={} min={} max={}",
+            report.len, report.mean, report.std_dev, report.min, report.max
+        )
+    }
+
+    /// Enables recording of the reports of the window after each pushed value, keeping the reports of the last `len` values.
+    /// The recorded reports are available using the `trajectory` method.
+    pub fn with_trajectory(mut self, len: usize) -> Self {
+        self.trajectory = Some(Trajectory {
+            len,
+            reports: Vec::with_capacity(2 * len),
+            report: Self::report,
+        });
+        self
+    }
+}
+
+/// Formats the compact summary written by the `fmt_summary` method, such as `n=3 mean=2 sd=1 min=1 max=3`.
+/// The precision of the formatter applies to the statistics, `{:.2}` formats them with two decimal places.
+impl<T, E, const WINDOW_SIZE: usize, O, S> Display for RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float + Display,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let precision = match f.precision() {
+            Some(precision) => precision,
+            None => return self.fmt_summary(f),
+        };
+
+        let report = self.report();
+        write!(
+            f,
+            "n={} mean={:.*} sd={:.*} min={:.*} max={:.*}",
+            report.len,
+            precision,
+            report.mean,
+            precision,
+            report.std_dev,
+            precision,
+            report.min,
+            precision,
+            report.max
+        )
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Returns the reports recorded after each of the last pushed values, the oldest first.
+    /// The trajectory is empty unless enabled using the `with_trajectory` method.
+    pub fn trajectory(&self) -> &[Report<O>] {
+        self.trajectory
+            .as_ref()
+            .map_or(&[], |trajectory| trajectory.reports())
+    }
+
+    /// Records the report of the window to the trajectory if enabled.
+    pub(crate) fn record_trajectory(&mut self) {
+        if let Some(report) = self.trajectory.as_ref().map(|trajectory| trajectory.report) {
+            let report = report(self);
+            if let Some(trajectory) = self.trajectory.as_mut() {
+                trajectory.record(report);
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{BigEndian, LittleEndian, Report, RollingStats, Statistics, StatsDelta};
+    use std::io::Write;
+
+    #[test]
+    fn report() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::default();
+        let _ = roller.write(&[0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2]).unwrap();
+
+        assert_eq!(
+            roller.report(),
+            Report {
+                len: 3,
+                mean: 2.0,
+                std_dev: 1.0,
+                min: 1.0,
+                max: 3.0
+            }
+        );
+    }
+
+    #[test]
+    fn fmt_summary() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::default();
+        let _ = roller.write(&[0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2]).unwrap();
+
+        let mut summary = String::new();
+        roller.fmt_summary(&mut summary).unwrap();
+        assert_eq!(summary, "n=3 mean=2 sd=1 min=1 max=3");
+    }
+
+    #[test]
+    fn display() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::default();
+        let _ = roller.write(&[0, 0, 0, 3, 0, 0, 0, 1, 0, 0, 0, 2]).unwrap();
+
+        assert_eq!(format!("{}", roller), "n=3 mean=2 sd=1 min=1 max=3");
+        assert_eq!(
+            format!("{:.2}", roller),
+            "n=3 mean=2.00 sd=1.00 min=1.00 max=3.00"
+        );
+    }
+
+    #[test]
+    fn trajectory() {
+        let mut roller = RollingStats::<i32, BigEndian, 2>::new().with_trajectory(3);
+        assert!(roller.trajectory().is_empty());
+
+        let _ = roller
+            .write(&[0, 0, 0, 1, 0, 0, 0, 3, 0, 0, 0, 5, 0, 0, 0, 7, 0, 0, 0, 9])
+            .unwrap();
+
+        let means: Vec<f32> = roller.trajectory().iter().map(|r| r.mean).collect();
+        assert_eq!(means, vec![4.0, 6.0, 8.0]);
+        assert!(roller.trajectory().iter().all(|r| r.len == 2));
+    }
+
+    #[test]
+    fn compare() {
+        let live: RollingStats<i32, BigEndian, 4> = [1, 3, 5, 7].iter().copied().collect();
+        let baseline: RollingStats<f32, LittleEndian, 2> = [1.0, 3.0].iter().copied().collect();
+
+        assert_eq!(
+            live.compare(&baseline),
+            StatsDelta {
+                len: 2,
+                mean: 2.0,
+                std_dev: live.std_dev() - baseline.std_dev(),
+            }
+        );
+        assert_eq!(baseline.compare(&live).len, -2);
+    }
+
+    #[test]
+    fn windows() {
+        let roller: RollingStats<i32, BigEndian, 5> = [1, 5, 6, 7, 11].iter().copied().collect();
+
+        let means: Vec<f32> = roller.windows(3).map(|report| report.mean).collect();
+        assert_eq!(means, vec![4.0, 6.0, 8.0]);
+        let quietest = roller
+            .windows(2)
+            .min_by(|a, b| a.std_dev.partial_cmp(&b.std_dev).unwrap())
+            .unwrap();
+        assert_eq!((quietest.min, quietest.max), (5.0, 6.0));
+        assert_eq!(roller.windows(5).next(), Some(roller.report()));
+        assert_eq!(roller.windows(6).count(), 0);
+    }
+}
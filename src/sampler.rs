@@ -0,0 +1,75 @@
+//! Abstraction of sampling from the normal distribution, used for generating random numbers resembling the data in the window.
+//!
+//! The abstraction decouples the sampling from the `rand::thread_rng` random number generator,
+//! so that users can provide their own source of randomness, for example one available on embedded targets.
+
+use crate::{convertfloat::LossyFloatConvertible, RollingStats, Statistics, WindowStorage};
+use core::ops::Add;
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+
+/// Trait utilized for sampling from the normal distribution.
+/// `O` denotes the float type of the samples.
+pub trait GaussianSampler<O> {
+    /// Returns a number from the normal distribution specified by the mean and standard deviation.
+    fn sample(&mut self, mean: O, std_dev: O) -> O;
+}
+
+/// The ThreadRngSampler struct represents sampling based on the `rand::thread_rng` random number generator, available with the `std` feature.
+/// # Examples
+/// ```
+/// use rolling_stats::{GaussianSampler, ThreadRngSampler};
+///
+/// assert_eq!(ThreadRngSampler.sample(1.0f32, 0.0), 1.0);
+/// ```
+#[cfg(feature = "std")]
+pub struct ThreadRngSampler;
+
+#[cfg(feature = "std")]
+impl<O> GaussianSampler<O> for ThreadRngSampler
+where
+    O: Float,
+    StandardNormal: Distribution<O>,
+{
+    fn sample(&mut self, mean: O, std_dev: O) -> O {
+        let dist = rand_distr::Normal::new(mean, std_dev).unwrap();
+        dist.sample(&mut rand::thread_rng())
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    /// Returns a number from a normal distribution specified by the mean and standard deviation of the window,
+    /// sampled using the provided sampler.
+    pub fn rand_with<G: GaussianSampler<O>>(&self, sampler: &mut G) -> O {
+        sampler.sample(self.mean(), self.std_dev())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BigEndian;
+    use approx::*;
+
+    /// Sampler returning a value one standard deviation above the mean.
+    struct OneSigmaSampler;
+
+    impl GaussianSampler<f32> for OneSigmaSampler {
+        fn sample(&mut self, mean: f32, std_dev: f32) -> f32 {
+            mean + std_dev
+        }
+    }
+
+    #[test]
+    fn rand_with() {
+        let mut roller = RollingStats::<i32, BigEndian, 3>::default();
+        let _ = roller.feed(&[0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4]).unwrap();
+
+        assert_abs_diff_eq!(roller.rand_with(&mut OneSigmaSampler), 4.0);
+    }
+}
@@ -0,0 +1,143 @@
+//! A window shared between threads, one or more of them writing the raw data and any number of them reading the statistics.
+
+use crate::{
+    convertfloat::LossyFloatConvertible, ConverterFromRaw, Error, Report, RollingStats,
+    WindowStorage,
+};
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+use std::{
+    collections::VecDeque,
+    io::Write,
+    ops::Add,
+    sync::{Mutex, PoisonError, RwLock},
+};
+
+/// A window with interior mutability, meant to be shared between threads using an `Arc`.
+///
+/// The report of the window is published after each write as a snapshot, which the readers read without waiting for the writers to parse the data.
+/// A snapshot is always consistent - it is replaced as a whole once a write is finished.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, RollingStats, SharedRollingStats};
+/// use std::{io::Write, sync::Arc};
+///
+/// let shared = Arc::new(SharedRollingStats::new(RollingStats::<i32, BigEndian, 2>::new()));
+/// let writer = shared.clone();
+/// std::thread::spawn(move || (&*writer).write_all(&[0, 0, 0, 1, 0, 0, 0, 3]).unwrap())
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(shared.snapshot().mean, 2.0);
+/// ```
+pub struct SharedRollingStats<T, E, const WINDOW_SIZE: usize, O = f32, S = VecDeque<T>> {
+    stats: Mutex<RollingStats<T, E, WINDOW_SIZE, O, S>>,
+    snapshot: RwLock<Report<O>>,
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> SharedRollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    E: ConverterFromRaw<T>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    /// Creates a new shared window, publishing the report of the window.
+    pub fn new(stats: RollingStats<T, E, WINDOW_SIZE, O, S>) -> Self {
+        Self {
+            snapshot: RwLock::new(stats.report()),
+            stats: Mutex::new(stats),
+        }
+    }
+
+    /// Writes the raw data into the window and publishes the report of the window, the writes of multiple threads are serialized.
+    /// # Returns
+    /// Returns the number of consumed bytes the same way the `write` method of the window does.
+    /// The report is published even if the write fails, as the values parsed before the error are kept in the window.
+    pub fn write(&self, buf: &[u8]) -> Result<usize, Error> {
+        let mut stats = self.stats.lock().unwrap_or_else(PoisonError::into_inner);
+        let result = stats.write(buf);
+        let report = stats.report();
+        *self
+            .snapshot
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = report;
+
+        Ok(result?)
+    }
+
+    /// Returns the report of the window published by the last write.
+    pub fn snapshot(&self) -> Report<O> {
+        *self.snapshot.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Calls the closure with the window for the queries not covered by the report, waiting for the ongoing write to finish.
+    pub fn with_stats<R, F>(&self, f: F) -> R
+    where
+        F: FnOnce(&RollingStats<T, E, WINDOW_SIZE, O, S>) -> R,
+    {
+        f(&self.stats.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Returns the window.
+    pub fn into_inner(self) -> RollingStats<T, E, WINDOW_SIZE, O, S> {
+        self.stats
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Writing into a shared reference, the same way the `std::fs::File` allows, so that the shared window can be used with `std::io::copy`.
+impl<T, E, const WINDOW_SIZE: usize, O, S> Write for &SharedRollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    E: ConverterFromRaw<T>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(SharedRollingStats::write(self, buf)?)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BigEndian, Statistics};
+    use std::sync::Arc;
+
+    #[test]
+    fn publishes_snapshots() {
+        let shared = Arc::new(SharedRollingStats::new(
+            RollingStats::<i32, BigEndian, 100>::new(),
+        ));
+
+        let writers: Vec<_> = (0..4)
+            .map(|_| {
+                let shared = shared.clone();
+                std::thread::spawn(move || {
+                    for value in 1..=10i32 {
+                        let _ = shared.write(&value.to_be_bytes()).unwrap();
+                        let snapshot = shared.snapshot();
+                        assert!(snapshot.min >= 1.0 && snapshot.max <= 10.0);
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        assert_eq!(shared.snapshot().len, 40);
+        assert_eq!(shared.snapshot().mean, 5.5);
+        assert_eq!(
+            shared.with_stats(|stats| stats.std_dev()),
+            shared.snapshot().std_dev
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Analysis of the window as a time series, where the order of the items in the window represents time.
+
+use crate::{convertfloat::LossyFloatConvertible, order::interpolate, RollingStats, WindowStorage};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use rand_distr::num_traits::Float;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + LossyFloatConvertible<O>,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns the autocorrelation of the window at the specified lag.
+    /// # Returns
+    /// Returns `None` when the lag is not shorter than the window or when the window is constant.
+    pub fn autocorrelation(&self, lag: usize) -> Option<O> {
+        self.autocorrelations(lag).map(|acf| acf[lag])
+    }
+
+    /// Estimates the period of the signal in the window as the lag of the first local maximum of the autocorrelation,
+    /// that is significant - exceeds `1.96 / sqrt(len)`, the 95% bound of the autocorrelation of white noise.
+    /// Lags up to a half of the window length are scanned.
+    /// # Returns
+    /// Returns `None` when no significant peak was found.
+    pub fn dominant_period(&self) -> Option<usize> {
+        let max_lag = self.len() / 2;
+        let acf = self.autocorrelations((max_lag + 1).min(self.len().max(1) - 1))?;
+        let threshold = O::from(1.96).unwrap() / O::from(self.len()).unwrap().sqrt();
+
+        (1..=max_lag.min(acf.len().saturating_sub(2))).find(|lag| {
+            acf[*lag] > threshold && acf[*lag] > acf[*lag - 1] && acf[*lag] >= acf[*lag + 1]
+        })
+    }
+
+    /// Returns the high-side and low-side cumulative sums (CUSUM) of the window relative to the target mean.
+    /// The sums accumulate deviations exceeding the slack, being clamped at zero, so that they only grow while the mean is shifted.
+    /// # Arguments
+    /// * `target` - the expected mean of the window.
+    /// * `slack` - the allowed deviation from the target, usually a half of the shift to be detected.
+    ///
+    /// # Returns
+    /// Returns a tuple of the high-side and the low-side sum, both non-negative.
+    pub fn cusum(&self, target: O, slack: O) -> (O, O) {
+        self.buffer
+            .iter()
+            .fold((O::zero(), O::zero()), |(high, low), item| {
+                let value = item.convert();
+                (
+                    (high + value - target - slack).max(O::zero()),
+                    (low + target - slack - value).max(O::zero()),
+                )
+            })
+    }
+
+    /// Returns `true` when either of the cumulative sums of the window exceeds the threshold,
+    /// i.e. the mean of the window has shifted from the target. See the `cusum` method for the meaning of the arguments.
+    pub fn is_shifted(&self, target: O, slack: O, threshold: O) -> bool {
+        let (high, low) = self.cusum(target, slack);
+        high > threshold || low > threshold
+    }
+
+    /// Resamples the window into `m` evenly spaced points using linear interpolation,
+    /// the first and the last point corresponding to the oldest and the newest item.
+    /// # Returns
+    /// Returns `m` zeros for an empty window, `m` copies of the item for a window holding a single item.
+    pub fn resample(&self, m: usize) -> Vec<O> {
+        let values: Vec<O> = self.buffer.iter().map(|item| item.convert()).collect();
+        let step = O::from(values.len().max(1) - 1).unwrap() / O::from(m.max(2) - 1).unwrap();
+
+        (0..m)
+            .map(|i| interpolate(&values, O::from(i).unwrap() * step))
+            .collect()
+    }
+
+    /// Returns the autocorrelations of the window for lags from zero to `max_lag` inclusive.
+    fn autocorrelations(&self, max_lag: usize) -> Option<Vec<O>> {
+        if max_lag >= self.len() {
+            return None;
+        }
+
+        let values: Vec<O> = self.buffer.iter().map(|item| item.convert()).collect();
+        let mean =
+            values.iter().fold(O::zero(), |acc, v| acc + *v) / O::from(values.len()).unwrap();
+        let deviations: Vec<O> = values.iter().map(|v| *v - mean).collect();
+
+        let variance = deviations.iter().fold(O::zero(), |acc, d| acc + *d * *d);
+        if variance.is_zero() {
+            return None;
+        }
+
+        Some(
+            (0..=max_lag)
+                .map(|lag| {
+                    deviations
+                        .iter()
+                        .zip(deviations.iter().skip(lag))
+                        .fold(O::zero(), |acc, (a, b)| acc + *a * *b)
+                        / variance
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: PartialOrd,
+    S: WindowStorage<T>,
+{
+    /// Returns the number of monotonic runs - maximal rising or falling stretches of the window.
+    /// Steps between equal consecutive values don't change the direction of a run.
+    /// # Returns
+    /// Returns `0` for windows with less than two items and `1` for flat or monotonic windows.
+    pub fn monotonic_runs(&self) -> usize {
+        if self.len() < 2 {
+            return 0;
+        }
+
+        let mut direction = Ordering::Equal;
+        let mut runs = 1;
+        for (previous, next) in self.buffer.iter().zip(self.buffer.iter().skip(1)) {
+            let step = next.partial_cmp(previous).unwrap_or(Ordering::Equal);
+            if step == Ordering::Equal {
+                continue;
+            }
+            if direction != Ordering::Equal && step != direction {
+                runs += 1;
+            }
+            direction = step;
+        }
+
+        runs
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{LittleEndian, RollingStats};
+    use approx::*;
+    use std::io::Write;
+
+    fn roller<const N: usize>(data: &[i32]) -> RollingStats<i32, LittleEndian, N> {
+        let mut roller = RollingStats::default();
+        for value in data {
+            let _ = roller.write(&value.to_le_bytes()).unwrap();
+        }
+        roller
+    }
+
+    #[test]
+    fn autocorrelation() {
+        let ramp = roller::<4>(&[1, 2, 3, 4]);
+
+        assert_abs_diff_eq!(ramp.autocorrelation(0).unwrap(), 1.0);
+        assert_abs_diff_eq!(ramp.autocorrelation(1).unwrap(), 0.25);
+        assert!(ramp.autocorrelation(4).is_none());
+        assert!(roller::<4>(&[2, 2, 2]).autocorrelation(1).is_none());
+    }
+
+    #[test]
+    fn dominant_period() {
+        let periodic = roller::<24>(&[0, 2, 4, 2, 0, -2, -4, -2].repeat(3));
+        assert_eq!(periodic.dominant_period(), Some(8));
+
+        let trend = roller::<8>(&[1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(trend.dominant_period(), None);
+    }
+
+    #[test]
+    fn cusum() {
+        let stable = roller::<6>(&[10, 11, 9, 10, 11, 9]);
+        assert_eq!(stable.cusum(10.0, 1.0), (0.0, 0.0));
+        assert!(!stable.is_shifted(10.0, 1.0, 4.0));
+
+        let raised = roller::<6>(&[10, 9, 13, 14, 13, 14]);
+        assert_eq!(raised.cusum(10.0, 1.0), (10.0, 0.0));
+        assert!(raised.is_shifted(10.0, 1.0, 4.0));
+
+        let lowered = roller::<6>(&[10, 6, 7, 13, 6, 6]);
+        assert_eq!(lowered.cusum(10.0, 1.0), (0.0, 7.0));
+        assert!(lowered.is_shifted(10.0, 1.0, 4.0));
+    }
+
+    #[test]
+    fn resample() {
+        assert_eq!(roller::<4>(&[]).resample(3), vec![0.0; 3]);
+        assert_eq!(roller::<4>(&[2]).resample(3), vec![2.0; 3]);
+        assert_eq!(
+            roller::<4>(&[0, 4]).resample(5),
+            vec![0.0, 1.0, 2.0, 3.0, 4.0]
+        );
+        assert_eq!(
+            roller::<5>(&[0, 1, 2, 3, 4]).resample(3),
+            vec![0.0, 2.0, 4.0]
+        );
+        assert!(roller::<4>(&[1, 2]).resample(0).is_empty());
+    }
+
+    #[test]
+    fn monotonic_runs() {
+        assert_eq!(roller::<4>(&[1]).monotonic_runs(), 0);
+        assert_eq!(roller::<4>(&[2, 2, 2]).monotonic_runs(), 1);
+        assert_eq!(roller::<4>(&[1, 2, 2, 3]).monotonic_runs(), 1);
+        assert_eq!(roller::<8>(&[1, 3, 2, 2, 0, 4, 5, 1]).monotonic_runs(), 4);
+    }
+}
@@ -0,0 +1,78 @@
+//! Exponential smoothing of the window, walking the window from the oldest item to the newest one.
+
+use crate::{convertfloat::LossyFloatConvertible, RollingStats, WindowStorage};
+use rand_distr::num_traits::Float;
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + LossyFloatConvertible<O>,
+    O: Float,
+    S: WindowStorage<T>,
+{
+    /// Returns the level and the trend of the window smoothed using the Holt's linear (double exponential smoothing) method.
+    /// The level is initialized to the oldest item and the trend to the difference of the two oldest items.
+    /// # Arguments
+    /// * `alpha` - the smoothing factor of the level, from 0 to 1.
+    /// * `beta` - the smoothing factor of the trend, from 0 to 1.
+    ///
+    /// # Returns
+    /// Returns a tuple of the level and the trend, zero trend is returned for a single item, zeros for an empty window.
+    pub fn holt(&self, alpha: O, beta: O) -> (O, O) {
+        let mut values = self.buffer.iter().map(|item| item.convert());
+        let mut level = match values.next() {
+            Some(first) => first,
+            None => return (O::zero(), O::zero()),
+        };
+        let mut values = values.peekable();
+        let mut trend = values.peek().map_or(O::zero(), |second| *second - level);
+
+        for value in values {
+            let previous_level = level;
+            level = alpha * value + (O::one() - alpha) * (level + trend);
+            trend = beta * (level - previous_level) + (O::one() - beta) * trend;
+        }
+
+        (level, trend)
+    }
+
+    /// Returns the forecast of the value `steps` items ahead of the newest item, based on the Holt's linear method.
+    /// See the `holt` method for the meaning of the arguments.
+    pub fn forecast(&self, alpha: O, beta: O, steps: usize) -> O {
+        let (level, trend) = self.holt(alpha, beta);
+        level + trend * O::from(steps).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, RollingStats};
+    use approx::*;
+
+    #[test]
+    fn holt() {
+        let mut roller = RollingStats::<i32, BigEndian, 4>::default();
+        assert_eq!(roller.holt(0.5, 0.5), (0.0, 0.0));
+
+        let _ = roller
+            .feed(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4])
+            .unwrap();
+
+        let (level, trend) = roller.holt(0.5, 0.5);
+        assert_abs_diff_eq!(level, 4.0);
+        assert_abs_diff_eq!(trend, 1.0);
+        assert_abs_diff_eq!(roller.forecast(0.5, 0.5, 2), 6.0);
+    }
+
+    #[test]
+    fn holt_smooths_noise() {
+        let mut roller = RollingStats::<i32, BigEndian, 4>::default();
+        let _ = roller
+            .feed(&[0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 4, 0, 0, 0, 4])
+            .unwrap();
+
+        // level: 0, 4, 6, 6.5; trend: 4, 4, 3, 1.75
+        let (level, trend) = roller.holt(0.5, 0.5);
+        assert_abs_diff_eq!(level, 6.5);
+        assert_abs_diff_eq!(trend, 1.75);
+    }
+}
@@ -0,0 +1,256 @@
+//! Splitting of the window into the ingestion and the query halves, connected by a lock-free single-producer single-consumer ring buffer.
+//! The ingestion half parses the raw data and never blocks, so it is suitable for real-time threads,
+//! the query half moves the parsed values into the window when the statistics are needed.
+
+use crate::{
+    partial_data_buffer::PartialDataBuffer, ConverterFromRaw, Error, RollingStats, WindowStorage,
+};
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use core::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+#[cfg(feature = "std")]
+use std::io::Write;
+
+/// A fixed capacity ring buffer of a single producer and a single consumer.
+/// The indices grow indefinitely and wrap around, the capacity is a power of two so that the wrapping keeps the slot positions.
+struct Ring<T> {
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    /// The index of the next value to be popped, written by the consumer only.
+    head: AtomicUsize,
+    /// The index of the next value to be pushed, written by the producer only.
+    tail: AtomicUsize,
+    /// The number of values not pushed as the buffer was full, or popped but refused by the window.
+    dropped: AtomicUsize,
+}
+
+// The slots are accessed by a single producer and a single consumer, each slot is handed over using the release and acquire orderings of the indices.
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+impl<T: Copy> Ring<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity.max(1).next_power_of_two())
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+        }
+    }
+
+    /// Pushes the value, or drops it if the buffer is full.
+    /// Called only by the single producer - the `Writer`, which is not `Clone` and pushes using a mutable reference.
+    fn push(&self, value: T) {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == self.slots.len() {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
+        // The slot is not between the head and the tail, so the consumer doesn't access it until the tail is released.
+        unsafe { (*self.slots[tail & (self.slots.len() - 1)].get()).write(value) };
+        self.tail.store(tail.wrapping_add(1), Ordering::Release);
+    }
+
+    /// Pops the oldest value.
+    /// Called only by the single consumer - the `Reader`, which is not `Clone` and pops using a mutable reference.
+    fn pop(&self) -> Option<T> {
+        let head = self.head.load(Ordering::Relaxed);
+        let tail = self.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+
+        // The slot is between the head and the tail, so it was initialized and the producer doesn't access it until the head is released.
+        let value =
+            unsafe { (*self.slots[head & (self.slots.len() - 1)].get()).assume_init_read() };
+        self.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+}
+
+/// The ingestion half of a split window, parsing the raw data written using the `std::io::Write` trait or the `feed` method.
+/// The parsed values are dropped and counted if the ring buffer is full, the writing never blocks.
+pub struct Writer<T, E> {
+    ring: Arc<Ring<T>>,
+    partial: PartialDataBuffer<T, E>,
+}
+
+impl<T, E> Writer<T, E> {
+    /// Returns the number of parsed values dropped as the ring buffer was full, or as the window refused them.
+    pub fn dropped(&self) -> usize {
+        self.ring.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T, E> Writer<T, E>
+where
+    T: Copy,
+    E: ConverterFromRaw<T>,
+{
+    /// Parses the raw data and pushes the parsed values to the ring buffer.
+    /// # Returns
+    /// Returns the length of the raw data, or the `Error::Conversion` error if the conversion fails.
+    pub fn feed(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let (reconstructed, remaining_buf) = self.partial.consume(buf);
+        if let Some(value) = reconstructed {
+            self.ring.push(value?);
+        }
+
+        for raw in remaining_buf.chunks_exact(self.partial.type_size()) {
+            self.ring.push(E::from_raw(raw)?);
+        }
+
+        Ok(buf.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, E> Write for Writer<T, E>
+where
+    T: Copy,
+    E: ConverterFromRaw<T>,
+{
+    /// Parses the raw data and pushes the parsed values to the ring buffer.
+    /// # Returns
+    /// Returns the length of the raw data, or an `InvalidData` error if the conversion fails.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(self.feed(buf)?)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The query half of a split window, moving the values parsed by the `Writer` into the window.
+pub struct Reader<T, E, const WINDOW_SIZE: usize, O = f32, S = VecDeque<T>> {
+    ring: Arc<Ring<T>>,
+    stats: RollingStats<T, E, WINDOW_SIZE, O, S>,
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> Reader<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    S: WindowStorage<T>,
+{
+    /// Moves the values parsed since the last update into the window, the same way the values written into the window are treated.
+    /// # Returns
+    /// Returns the window, or the error of accepting a value, in which case the rest of the values remain in the ring buffer.
+    /// The value failing to be accepted is dropped and counted by the `dropped` method.
+    pub fn update(&mut self) -> Result<&RollingStats<T, E, WINDOW_SIZE, O, S>, Error> {
+        while let Some(value) = self.ring.pop() {
+            if let Err(error) = self.stats.push(value) {
+                self.ring.dropped.fetch_add(1, Ordering::Relaxed);
+                return Err(error);
+            }
+        }
+
+        Ok(&self.stats)
+    }
+
+    /// Returns the window as of the last update.
+    pub fn stats(&self) -> &RollingStats<T, E, WINDOW_SIZE, O, S> {
+        &self.stats
+    }
+
+    /// Returns the window mutably, such as for clearing a window full with the `EvictionPolicy::RejectWhenFull` policy.
+    pub fn stats_mut(&mut self) -> &mut RollingStats<T, E, WINDOW_SIZE, O, S> {
+        &mut self.stats
+    }
+
+    /// Returns the number of parsed values dropped as the ring buffer was full, or as the window refused them.
+    pub fn dropped(&self) -> usize {
+        self.ring.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy,
+    S: WindowStorage<T>,
+{
+    /// Splits the window into the `Writer` parsing the raw data and the `Reader` querying the statistics,
+    /// connected by a lock-free ring buffer holding at least `capacity` parsed values.
+    /// The `Writer` can be moved to the ingestion thread, as it never blocks.
+    ///
+    /// The configuration of the window applies to the values moved into the window by the `Reader`,
+    /// incomplete data pending in the window are discarded.
+    pub fn split(mut self, capacity: usize) -> (Writer<T, E>, Reader<T, E, WINDOW_SIZE, O, S>) {
+        let ring = Arc::new(Ring::with_capacity(capacity));
+        let writer = Writer {
+            ring: ring.clone(),
+            partial: PartialDataBuffer::with_type_size(self.type_size()),
+        };
+        self.discard_pending();
+
+        (writer, Reader { ring, stats: self })
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{BigEndian, EvictionPolicy, Statistics};
+
+    #[test]
+    fn passes_values_through_ring() {
+        let roller = RollingStats::<i32, BigEndian, 3>::new().with_sentinels([0]);
+        let (mut writer, mut reader) = roller.split(2);
+
+        writer.write_all(&[0, 0, 0, 1, 0, 0]).unwrap();
+        writer.write_all(&[0, 0]).unwrap();
+        assert_eq!(reader.update().unwrap().buffer, vec![1]);
+        assert_eq!(reader.stats().skipped(), 1);
+
+        writer
+            .write_all(&[0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 4])
+            .unwrap();
+        assert_eq!(writer.dropped(), 1);
+        assert_eq!(reader.update().unwrap().buffer, vec![1, 2, 3]);
+        assert_eq!(reader.dropped(), 1);
+    }
+
+    #[test]
+    fn counts_refused_values() {
+        let roller = RollingStats::<i32, BigEndian, 1>::new()
+            .with_eviction_policy(EvictionPolicy::RejectWhenFull);
+        let (mut writer, mut reader) = roller.split(4);
+
+        writer
+            .write_all(&[0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 3])
+            .unwrap();
+        assert!(matches!(reader.update(), Err(Error::WindowFull)));
+        assert_eq!(reader.dropped(), 1);
+        assert_eq!(reader.stats().buffer, vec![1]);
+
+        reader.stats_mut().clear();
+        assert_eq!(reader.update().unwrap().buffer, vec![3]);
+        assert_eq!(writer.dropped(), 1);
+    }
+
+    #[test]
+    fn passes_values_between_threads() {
+        let (mut writer, mut reader) = RollingStats::<i32, BigEndian, 4>::new().split(1024);
+        let producer = std::thread::spawn(move || {
+            for value in 1..=1000i32 {
+                writer.write_all(&value.to_be_bytes()).unwrap();
+            }
+            writer
+        });
+
+        let mut seen = 0;
+        while seen < 1000 {
+            let stats = reader.update().unwrap();
+            seen = stats.total_count();
+        }
+        let writer = producer.join().unwrap();
+
+        assert_eq!(writer.dropped(), 0);
+        assert_eq!(reader.stats().mean(), 998.5);
+    }
+}
@@ -0,0 +1,98 @@
+//! Derived statistics measures implemented purely in terms of the `Statistics` trait,
+//! so that every implementor of the `Statistics` trait gets them for free.
+
+use crate::Statistics;
+use rand_distr::num_traits::Float;
+
+/// The `StatisticsExt` trait extends the `Statistics` trait with derived measures.
+/// The trait is implemented for all implementors of the `Statistics` trait.
+/// # Examples
+/// ```
+/// use rolling_stats::{LittleEndian, RollingStats, StatisticsExt};
+/// use approx::*;
+///
+/// let mut roller = RollingStats::<i32, LittleEndian, 3>::default();
+/// let _ = roller
+///     .feed(&[2, 0, 0, 0, 3, 0, 0, 0, 4, 0, 0, 0])
+///     .unwrap();
+/// assert_abs_diff_eq!(roller.z_score(5.0), 2.0);
+/// ```
+pub trait StatisticsExt<O = f32>: Statistics<O>
+where
+    O: Float,
+{
+    /// Returns the coefficient of variation, the ratio of the standard deviation to the mean.
+    fn coefficient_of_variation(&self) -> O {
+        self.std_dev() / self.mean()
+    }
+
+    /// Returns the standard error of the mean of a dataset of `n` items.
+    fn standard_error(&self, n: usize) -> O {
+        self.std_dev() / O::from(n).unwrap().sqrt()
+    }
+
+    /// Returns the mean squared error of a dataset of `n` items against a reference value,
+    /// computed as the population variance of the dataset plus the squared difference of its mean and the reference.
+    /// The standard deviation is expected to be the sample one (the delta degrees of freedom of one, the default of the `RollingStats`),
+    /// it is rescaled by `(n - 1) / n` to the population variance.
+    fn mse(&self, reference: O, n: usize) -> O {
+        let n = n.max(1);
+        let variance = self.std_dev().powi(2) * O::from(n - 1).unwrap() / O::from(n).unwrap();
+        variance + (self.mean() - reference).powi(2)
+    }
+
+    /// Returns the number of standard deviations the value lies from the mean of a dataset.
+    fn z_score(&self, value: O) -> O {
+        (value - self.mean()) / self.std_dev()
+    }
+}
+
+impl<S, O> StatisticsExt<O> for S
+where
+    S: Statistics<O> + ?Sized,
+    O: Float,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LittleEndian, RollingStats};
+    use approx::*;
+
+    /// Statistics of a dataset with a fixed mean and standard deviation.
+    struct Fixed;
+
+    impl Statistics for Fixed {
+        fn mean(&self) -> f32 {
+            4.0
+        }
+
+        fn std_dev(&self) -> f32 {
+            2.0
+        }
+    }
+
+    #[test]
+    fn derived_statistics() {
+        assert_abs_diff_eq!(Fixed.coefficient_of_variation(), 0.5);
+        assert_abs_diff_eq!(Fixed.standard_error(16), 0.5);
+        assert_abs_diff_eq!(Fixed.mse(1.0, 5), 12.2);
+        assert_abs_diff_eq!(Fixed.mse(1.0, 1), 9.0);
+        assert_abs_diff_eq!(Fixed.z_score(1.0), -1.5);
+    }
+
+    #[test]
+    fn mean_squared_error() {
+        let roller: RollingStats<i32, LittleEndian, 4> = (1..=4).collect();
+        // (1 + 4 + 9 + 16) / 4
+        assert_abs_diff_eq!(roller.mse(0.0, roller.len()), 7.5, epsilon = 1e-5);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn default_rand() {
+        let sample = Fixed.rand();
+        assert!(sample.is_finite());
+    }
+}
@@ -0,0 +1,417 @@
+//! The storage of the window items, abstracted by the `WindowStorage` trait.
+//!
+//! The window is stored in a `VecDeque` by default.
+//! The `ArrayDeque` is a fixed capacity double-ended queue stored inline in an array, so that the items of the window are never reallocated.
+//!
+//! Only the storage of the items is inline, the crate still requires an allocator - the buffer of incomplete raw data,
+//! the observers and the optional state, such as the mode tracking or the tumbling windows, are allocated on the heap.
+
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{
+    iter::{Chain, FromIterator},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    slice,
+};
+
+/// The storage of the window items - a queue the values are pushed to at the back and evicted from at the front.
+///
+/// The window evicts the oldest item before pushing a new one into a full window,
+/// the size of the window is limited to the capacity of a fixed capacity storage, so that the storage never overflows.
+pub trait WindowStorage<T>: Default {
+    /// The iterator over the items, the oldest first.
+    type Iter<'a>: DoubleEndedIterator<Item = &'a T> + Clone
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// Appends the item to the back of the queue.
+    fn push_back(&mut self, value: T);
+
+    /// Removes the oldest item of the queue.
+    fn pop_front(&mut self) -> Option<T>;
+
+    /// Returns an iterator over the items, the oldest first.
+    fn iter(&self) -> Self::Iter<'_>;
+
+    /// Returns the number of items in the queue.
+    fn len(&self) -> usize;
+
+    /// Returns the items as two slices, the oldest items first, the second slice is non-empty only if the items wrap around.
+    fn as_slices(&self) -> (&[T], &[T]);
+
+    /// Rearranges the items so that they are stored in a single slice, without allocating.
+    /// # Returns
+    /// Returns the items, the oldest first.
+    fn make_contiguous(&mut self) -> &mut [T];
+
+    /// Returns `true` if the queue holds no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximal number of items of a fixed capacity storage, `None` if the storage grows as needed.
+    fn fixed_capacity(&self) -> Option<usize> {
+        None
+    }
+
+    /// Reserves the capacity for at least `additional` more items, if the storage grows.
+    fn reserve(&mut self, additional: usize) {
+        let _ = additional;
+    }
+
+    /// Removes all of the items.
+    fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+    }
+}
+
+impl<T> WindowStorage<T> for VecDeque<T> {
+    type Iter<'a>
+        = alloc::collections::vec_deque::Iter<'a, T>
+    where
+        T: 'a;
+
+    fn push_back(&mut self, value: T) {
+        VecDeque::push_back(self, value)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        VecDeque::pop_front(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        VecDeque::iter(self)
+    }
+
+    fn len(&self) -> usize {
+        VecDeque::len(self)
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        VecDeque::as_slices(self)
+    }
+
+    fn make_contiguous(&mut self) -> &mut [T] {
+        VecDeque::make_contiguous(self)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        VecDeque::reserve(self, additional)
+    }
+
+    fn clear(&mut self) {
+        VecDeque::clear(self)
+    }
+}
+
+/// An iterator removing the items of the storage, the oldest first, returned by the `RollingStats::drain` method.
+/// The items not yet yielded are removed once the iterator is dropped.
+pub struct Drain<'a, T, S: WindowStorage<T>> {
+    storage: &'a mut S,
+    _t: PhantomData<T>,
+}
+
+impl<'a, T, S: WindowStorage<T>> Drain<'a, T, S> {
+    pub(crate) fn new(storage: &'a mut S) -> Self {
+        Self {
+            storage,
+            _t: PhantomData,
+        }
+    }
+}
+
+impl<T, S: WindowStorage<T>> Iterator for Drain<'_, T, S> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.storage.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.storage.len(), Some(self.storage.len()))
+    }
+}
+
+impl<T, S: WindowStorage<T>> ExactSizeIterator for Drain<'_, T, S> {}
+
+impl<T, S: WindowStorage<T>> Drop for Drain<'_, T, S> {
+    fn drop(&mut self) {
+        self.storage.clear();
+    }
+}
+
+/// A ring of at most `N` items stored in an array.
+/// The items are kept in the slots `head..head + len`, wrapping around the end of the array.
+///
+/// The items are not allocated, the rest of the window still is, see the module documentation.
+/// A window stored in the `ArrayDeque` holds at most `N` items, even if its window size is larger.
+/// Use `N` equal to the `WINDOW_SIZE` of the window.
+pub struct ArrayDeque<T, const N: usize> {
+    slots: [MaybeUninit<T>; N],
+    head: usize,
+    len: usize,
+}
+
+/// An iterator over the items of the `ArrayDeque`, the oldest first.
+pub type Iter<'a, T> = Chain<slice::Iter<'a, T>, slice::Iter<'a, T>>;
+
+impl<T, const N: usize> ArrayDeque<T, N> {
+    /// Creates an empty deque.
+    pub fn new() -> Self {
+        Self {
+            // an array of `MaybeUninit` doesn't require initialization
+            slots: unsafe { MaybeUninit::<[MaybeUninit<T>; N]>::uninit().assume_init() },
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of items in the deque.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the deque holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Appends the item to the back of the deque.
+    /// The item is dropped if the deque is full, the caller is expected to make room for it first.
+    pub fn push_back(&mut self, value: T) {
+        if self.len == N {
+            return;
+        }
+
+        self.slots[(self.head + self.len) % N].write(value);
+        self.len += 1;
+    }
+
+    /// Removes the oldest item of the deque.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+
+        // the slot at the head is initialized and is no longer considered part of the deque once read
+        let value = unsafe { self.slots[self.head].assume_init_read() };
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Removes all of the items.
+    pub fn clear(&mut self) {
+        while self.pop_front().is_some() {}
+        self.head = 0;
+    }
+
+    /// Returns the items as two slices, the oldest items first, the second slice is non-empty only if the items wrap around the end of the array.
+    pub fn as_slices(&self) -> (&[T], &[T]) {
+        let first_len = self.len.min(N - self.head);
+        // the slots `head..head + first_len` and `0..len - first_len` are initialized
+        unsafe {
+            let slots = self.slots.as_ptr() as *const T;
+            (
+                slice::from_raw_parts(slots.add(self.head), first_len),
+                slice::from_raw_parts(slots, self.len - first_len),
+            )
+        }
+    }
+
+    /// Rotates the items so that they start at the beginning of the array.
+    /// # Returns
+    /// Returns the items, the oldest first.
+    pub fn make_contiguous(&mut self) -> &mut [T] {
+        self.slots.rotate_left(self.head);
+        self.head = 0;
+        // the slots `0..len` are initialized after the rotation
+        unsafe { slice::from_raw_parts_mut(self.slots.as_mut_ptr() as *mut T, self.len) }
+    }
+
+    /// Returns an iterator over the items, the oldest first.
+    pub fn iter(&self) -> Iter<'_, T> {
+        let (first, second) = self.as_slices();
+        first.iter().chain(second.iter())
+    }
+}
+
+impl<T, const N: usize> WindowStorage<T> for ArrayDeque<T, N> {
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn push_back(&mut self, value: T) {
+        ArrayDeque::push_back(self, value)
+    }
+
+    fn pop_front(&mut self) -> Option<T> {
+        ArrayDeque::pop_front(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        ArrayDeque::iter(self)
+    }
+
+    fn len(&self) -> usize {
+        ArrayDeque::len(self)
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        ArrayDeque::as_slices(self)
+    }
+
+    fn make_contiguous(&mut self) -> &mut [T] {
+        ArrayDeque::make_contiguous(self)
+    }
+
+    fn fixed_capacity(&self) -> Option<usize> {
+        Some(N)
+    }
+
+    fn clear(&mut self) {
+        ArrayDeque::clear(self)
+    }
+}
+
+impl<T, const N: usize> Drop for ArrayDeque<T, N> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T, const N: usize> Default for ArrayDeque<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for ArrayDeque<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T, const N: usize> Extend<T> for ArrayDeque<T, N> {
+    /// Appends the items, keeping the last `N` of them once the deque is full.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            if self.len == N {
+                self.pop_front();
+            }
+            self.push_back(value);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for ArrayDeque<T, N> {
+    /// Collects the last `N` items of the iterator.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut deque = Self::new();
+        deque.extend(iter);
+        deque
+    }
+}
+
+impl<T, const N: usize> From<Vec<T>> for ArrayDeque<T, N> {
+    fn from(values: Vec<T>) -> Self {
+        values.into_iter().collect()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a ArrayDeque<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq<Vec<T>> for ArrayDeque<T, N> {
+    fn eq(&self, other: &Vec<T>) -> bool {
+        self.len == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for ArrayDeque<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: core::fmt::Debug, const N: usize> core::fmt::Debug for ArrayDeque<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{LittleEndian, RollingStats, Statistics};
+    use std::{io::Write, rc::Rc};
+
+    #[test]
+    fn array_storage_window() {
+        let mut roller = RollingStats::<i32, LittleEndian, 3, f32, ArrayDeque<i32, 3>>::new();
+        for value in [1i32, 2, 3, 4, 5] {
+            roller.write_all(&value.to_le_bytes()).unwrap();
+        }
+
+        assert_eq!(roller.buffer, vec![3, 4, 5]);
+        assert_eq!(roller.mean(), 4.0);
+        assert_eq!(roller.evicted(), 2);
+    }
+
+    #[test]
+    fn window_limited_to_capacity() {
+        let mut roller = RollingStats::<i32, LittleEndian, 5, f32, ArrayDeque<i32, 3>>::new();
+        assert_eq!(roller.window_size(), 3);
+        for value in 1..=6i32 {
+            roller.write_all(&value.to_le_bytes()).unwrap();
+        }
+
+        assert_eq!(roller.buffer, vec![4, 5, 6]);
+        assert_eq!(roller.mean(), 5.0);
+        assert_eq!((roller.total_count(), roller.evicted()), (6, 3));
+    }
+
+    #[test]
+    fn wraps_around() {
+        let mut deque = ArrayDeque::<i32, 3>::new();
+        deque.extend([1, 2, 3]);
+        assert_eq!(deque.pop_front(), Some(1));
+        deque.push_back(4);
+        deque.push_back(5);
+
+        assert_eq!(deque, vec![2, 3, 4]);
+        assert_eq!(deque.as_slices(), (&[2, 3][..], &[4][..]));
+
+        deque.extend([6, 7]);
+        assert_eq!(deque, vec![4, 6, 7]);
+        assert_eq!(
+            deque.iter().rev().copied().collect::<Vec<_>>(),
+            vec![7, 6, 4]
+        );
+
+        assert_eq!(deque.make_contiguous(), &[4, 6, 7]);
+        assert_eq!(deque.as_slices(), (&[4, 6, 7][..], &[][..]));
+        deque.pop_front();
+        deque.push_back(8);
+        assert_eq!(deque, vec![6, 7, 8]);
+    }
+
+    #[test]
+    fn drops_items() {
+        let item = Rc::new(());
+        let mut deque = ArrayDeque::<Rc<()>, 2>::new();
+        deque.extend([item.clone(), item.clone(), item.clone()]);
+        assert_eq!(Rc::strong_count(&item), 3);
+
+        drop(deque);
+        assert_eq!(Rc::strong_count(&item), 1);
+    }
+}
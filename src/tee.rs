@@ -0,0 +1,89 @@
+//! Tee passes the raw data through to a writer, such as the `RollingStats`, while recording them to a secondary writer.
+//! Useful for capturing the raw data for an offline replay.
+
+use std::io::Write;
+
+/// A writer forwarding the raw data to the inner writer and recording the bytes consumed by the inner writer to the recorder.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, RollingStats, Statistics, Tee};
+/// use std::io::Write;
+///
+/// let mut tee = Tee::new(RollingStats::<i32, BigEndian, 2>::new(), Vec::new());
+/// tee.write_all(&[0, 0, 0, 1, 0, 0, 0, 3]).unwrap();
+///
+/// let (roller, recording) = tee.into_inner();
+/// assert_eq!(roller.mean(), 2.0);
+/// assert_eq!(recording, vec![0, 0, 0, 1, 0, 0, 0, 3]);
+/// ```
+pub struct Tee<W, R> {
+    inner: W,
+    recorder: R,
+}
+
+impl<W, R> Tee<W, R> {
+    /// Creates a new `Tee` forwarding the raw data to `inner` and recording them to `recorder`.
+    pub fn new(inner: W, recorder: R) -> Self {
+        Self { inner, recorder }
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the recorder.
+    pub fn recorder(&self) -> &R {
+        &self.recorder
+    }
+
+    /// Returns the inner writer and the recorder.
+    pub fn into_inner(self) -> (W, R) {
+        (self.inner, self.recorder)
+    }
+}
+
+impl<W, R> Write for Tee<W, R>
+where
+    W: Write,
+    R: Write,
+{
+    /// Writes the raw data to the inner writer, then records the consumed bytes.
+    /// # Returns
+    /// Returns the number of bytes consumed by the inner writer, or an error returned by either of the writers.
+    /// The bytes are not recorded if the inner writer returns an error.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.recorder.write_all(&buf[..written])?;
+
+        Ok(written)
+    }
+
+    /// Flushes both of the writers.
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()?;
+        self.recorder.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BigEndian, EvictionPolicy, RollingStats};
+
+    #[test]
+    fn records_consumed_bytes() {
+        let roller = RollingStats::<i32, BigEndian, 1>::new()
+            .with_eviction_policy(EvictionPolicy::RejectWhenFull);
+        let mut tee = Tee::new(roller, Vec::new());
+
+        assert_eq!(tee.write(&[0, 0, 0, 1, 0, 0]).unwrap(), 4);
+        assert_eq!(tee.get_ref().len(), 1);
+        assert_eq!(tee.recorder(), &vec![0, 0, 0, 1]);
+    }
+}
@@ -0,0 +1,193 @@
+//! Decoding of the binary data transported as text, such as hex or base64 encoded frames received over JSON or AT commands.
+//! The decoded raw data are passed to the inner writer, such as the `RollingStats`.
+
+use crate::Error;
+use std::io::Write;
+
+/// Denotes the text encoding of the binary data.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// Two hexadecimal digits per byte, case insensitive.
+    Hex,
+    /// The standard base64 alphabet, with optional padding.
+    Base64,
+}
+
+impl TextEncoding {
+    /// Returns the number of characters encoding a whole number of bytes.
+    fn group_size(&self) -> usize {
+        match self {
+            TextEncoding::Hex => 2,
+            TextEncoding::Base64 => 4,
+        }
+    }
+
+    /// Returns the value of a single character, or `None` if the character is not a part of the alphabet.
+    fn value(&self, character: u8) -> Option<u8> {
+        match (self, character) {
+            (TextEncoding::Hex, b'0'..=b'9') => Some(character - b'0'),
+            (TextEncoding::Hex, b'a'..=b'f') => Some(character - b'a' + 10),
+            (TextEncoding::Hex, b'A'..=b'F') => Some(character - b'A' + 10),
+            (TextEncoding::Base64, b'A'..=b'Z') => Some(character - b'A'),
+            (TextEncoding::Base64, b'a'..=b'z') => Some(character - b'a' + 26),
+            (TextEncoding::Base64, b'0'..=b'9') => Some(character - b'0' + 52),
+            (TextEncoding::Base64, b'+') => Some(62),
+            (TextEncoding::Base64, b'/') => Some(63),
+            _ => None,
+        }
+    }
+
+    /// Decodes a whole group of characters into the output.
+    fn decode(&self, group: &[u8], output: &mut Vec<u8>) -> Result<(), Error> {
+        let padding = group.iter().rev().take_while(|c| **c == b'=').count();
+        if (*self == TextEncoding::Hex && padding > 0) || padding > 2 {
+            return Err(Error::InvalidEncoding);
+        }
+
+        let mut bits = 0u32;
+        for character in &group[..group.len() - padding] {
+            bits =
+                bits << self.bits() | self.value(*character).ok_or(Error::InvalidEncoding)? as u32;
+        }
+        bits <<= padding * self.bits();
+
+        let bytes = group.len() * self.bits() / 8;
+        let decoded = bits.to_be_bytes();
+        output.extend_from_slice(&decoded[4 - bytes..4 - padding]);
+        Ok(())
+    }
+
+    /// Returns the number of bits encoded by a single character.
+    fn bits(&self) -> usize {
+        match self {
+            TextEncoding::Hex => 4,
+            TextEncoding::Base64 => 6,
+        }
+    }
+}
+
+/// A writer decoding the text encoded binary data and writing the decoded raw data to the inner writer.
+/// Characters of an incomplete group are stored until the rest of the group is written, whitespace is ignored.
+/// # Examples
+/// ```
+/// use rolling_stats::{BigEndian, RollingStats, Statistics, TextDecoder};
+/// use std::io::Write;
+///
+/// let mut decoder = TextDecoder::hex(RollingStats::<i32, BigEndian, 2>::new());
+/// decoder.write_all(b"00000001 000").unwrap();
+/// decoder.write_all(b"00003").unwrap();
+/// assert_eq!(decoder.get_ref().mean(), 2.0);
+/// ```
+pub struct TextDecoder<W> {
+    inner: W,
+    encoding: TextEncoding,
+    pending: Vec<u8>,
+}
+
+impl<W> TextDecoder<W> {
+    /// Creates a new decoder of the binary data encoded using the `encoding`.
+    pub fn new(inner: W, encoding: TextEncoding) -> Self {
+        Self {
+            inner,
+            encoding,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Creates a new decoder of the hex encoded binary data.
+    pub fn hex(inner: W) -> Self {
+        Self::new(inner, TextEncoding::Hex)
+    }
+
+    /// Creates a new decoder of the base64 encoded binary data.
+    pub fn base64(inner: W) -> Self {
+        Self::new(inner, TextEncoding::Base64)
+    }
+
+    /// Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Returns a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Returns the inner writer, discarding the characters of an incomplete group.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Returns the number of characters of an incomplete group waiting for the rest of the group.
+    pub fn pending_characters(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl<W: Write> Write for TextDecoder<W> {
+    /// Decodes the text and writes all of the decoded raw data to the inner writer.
+    /// # Returns
+    /// Returns the length of the text, or an `InvalidData` error if the text contains characters not belonging to the encoding.
+    /// The groups preceding the invalid one are written to the inner writer, the pending characters are discarded.
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut decoded = Vec::with_capacity(buf.len() / 2);
+        let group_size = self.encoding.group_size();
+
+        for character in buf.iter().filter(|c| !c.is_ascii_whitespace()) {
+            self.pending.push(*character);
+            if self.pending.len() == group_size {
+                let result = self.encoding.decode(&self.pending, &mut decoded);
+                self.pending.clear();
+                if let Err(error) = result {
+                    self.inner.write_all(&decoded)?;
+                    return Err(error.into());
+                }
+            }
+        }
+
+        self.inner.write_all(&decoded)?;
+        Ok(buf.len())
+    }
+
+    /// Flushes the inner writer.
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_hex() {
+        let mut decoder = TextDecoder::hex(Vec::new());
+        decoder.write_all(b"0aFf 1").unwrap();
+        assert_eq!(decoder.pending_characters(), 1);
+        decoder.write_all(b"0\n").unwrap();
+
+        assert_eq!(decoder.into_inner(), vec![0x0a, 0xff, 0x10]);
+    }
+
+    #[test]
+    fn decodes_base64() {
+        let mut decoder = TextDecoder::base64(Vec::new());
+        decoder.write_all(b"AAAAAQ").unwrap();
+        decoder.write_all(b"==AAEC").unwrap();
+        decoder.write_all(b"/w=").unwrap();
+        decoder.write_all(b"=").unwrap();
+
+        assert_eq!(decoder.into_inner(), vec![0, 0, 0, 1, 0, 1, 2, 0xff]);
+    }
+
+    #[test]
+    fn rejects_invalid_characters() {
+        let mut decoder = TextDecoder::hex(Vec::new());
+        let error = decoder.write(b"01zz02").unwrap_err();
+
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(decoder.get_ref(), &vec![0x01]);
+        assert_eq!(decoder.pending_characters(), 0);
+    }
+}
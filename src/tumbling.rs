@@ -0,0 +1,155 @@
+//! Tumbling sub-windows, summarizing every `len` consecutive values independently of the rolling window,
+//! so that both the rolling view and the discrete aggregates, such as per-second ones, are available.
+
+use crate::{
+    convertfloat::LossyFloatConvertible, report::summarize, Report, RollingStats, WindowStorage,
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::ops::Add;
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+
+/// Collects the values of the current tumbling window and the reports of the finished ones.
+pub(crate) struct Tumbling<T, O> {
+    len: usize,
+    values: Vec<T>,
+    /// The maximal number of the queued reports.
+    capacity: usize,
+    /// The number of the reports dropped from the full queue.
+    dropped: usize,
+    reports: VecDeque<Report<O>>,
+    /// Creates the report of the finished tumbling window, captured when enabling the tumbling windows, so that pushing values doesn't require the statistics bounds.
+    summarize: fn(&[T], usize, Option<&O>) -> Report<O>,
+}
+
+impl<T: Clone, O: Clone> Clone for Tumbling<T, O> {
+    fn clone(&self) -> Self {
+        Self {
+            len: self.len,
+            values: self.values.clone(),
+            capacity: self.capacity,
+            dropped: self.dropped,
+            reports: self.reports.clone(),
+            summarize: self.summarize,
+        }
+    }
+}
+
+impl<T, O> Tumbling<T, O> {
+    pub(crate) fn clear(&mut self) {
+        self.values.clear();
+        self.dropped = 0;
+        self.reports.clear();
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    O: Float,
+    StandardNormal: Distribution<O>,
+    S: WindowStorage<T>,
+{
+    /// Enables the tumbling windows, finishing a report of every `len` consecutive values entering the window.
+    /// The tumbling windows don't overlap and are independent of the size of the rolling window,
+    /// the reports of the finished ones are queued until taken using the `take_tumbling_reports` method.
+    /// At most `capacity` reports are queued, the oldest ones are dropped once the queue is full and counted by the `dropped_tumbling_reports` method.
+    /// Setting `len` to zero disables the tumbling windows.
+    pub fn with_tumbling_window(mut self, len: usize, capacity: usize) -> Self {
+        self.tumbling = if len > 0 {
+            Some(Tumbling {
+                len,
+                values: Vec::with_capacity(len),
+                capacity,
+                dropped: 0,
+                reports: VecDeque::with_capacity(capacity.min(64)),
+                summarize: |values, ddof, decay| {
+                    summarize(values.iter().copied(), ddof, decay.copied())
+                },
+            })
+        } else {
+            None
+        };
+        self
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    S: WindowStorage<T>,
+{
+    /// Returns the reports of the tumbling windows finished since the last call, the oldest first.
+    /// The reports are queued unless taken, the queue is empty unless enabled using the `with_tumbling_window` method.
+    pub fn take_tumbling_reports(&mut self) -> Vec<Report<O>> {
+        self.tumbling
+            .as_mut()
+            .map_or_else(Vec::new, |tumbling| tumbling.reports.drain(..).collect())
+    }
+
+    /// Returns the number of the reports dropped from the full queue since the window was created or cleared.
+    pub fn dropped_tumbling_reports(&self) -> usize {
+        self.tumbling
+            .as_ref()
+            .map_or(0, |tumbling| tumbling.dropped)
+    }
+
+    /// Adds the value to the current tumbling window if enabled, finishing its report once full.
+    pub(crate) fn record_tumbling(&mut self, value: T) {
+        let ddof = self.ddof;
+        if let Some(tumbling) = self.tumbling.as_mut() {
+            tumbling.values.push(value);
+            if tumbling.values.len() == tumbling.len {
+                let report = (tumbling.summarize)(&tumbling.values, ddof, self.decay.as_ref());
+                tumbling.values.clear();
+                if tumbling.reports.len() >= tumbling.capacity {
+                    tumbling.dropped += 1;
+                    if tumbling.reports.pop_front().is_none() {
+                        return;
+                    }
+                }
+                tumbling.reports.push_back(report);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{LittleEndian, RollingStats, Statistics};
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn tumbling_reports() {
+        let mut roller = RollingStats::<i32, LittleEndian, 4>::new().with_tumbling_window(2, 8);
+        for value in [1i32, 3, 5, 9, 11] {
+            roller.feed(&value.to_le_bytes()).unwrap();
+        }
+
+        let reports = roller.take_tumbling_reports();
+        let means: Vec<f32> = reports.iter().map(|report| report.mean).collect();
+        assert_eq!(means, vec![2.0, 7.0]);
+        assert!(reports.iter().all(|report| report.len == 2));
+        assert_eq!(reports[1].min, 5.0);
+        assert_eq!(roller.mean(), 7.0);
+        assert!(roller.take_tumbling_reports().is_empty());
+
+        roller.feed(&13i32.to_le_bytes()).unwrap();
+        assert_eq!(roller.take_tumbling_reports()[0].mean, 12.0);
+    }
+
+    #[test]
+    fn drops_oldest_reports() {
+        let mut roller = RollingStats::<i32, LittleEndian, 4>::new().with_tumbling_window(1, 2);
+        roller.extend(1..=5);
+
+        assert_eq!(roller.dropped_tumbling_reports(), 3);
+        let means: Vec<f32> = roller
+            .take_tumbling_reports()
+            .iter()
+            .map(|report| report.mean)
+            .collect();
+        assert_eq!(means, vec![4.0, 5.0]);
+
+        roller.clear();
+        assert_eq!(roller.dropped_tumbling_reports(), 0);
+    }
+}
@@ -0,0 +1,110 @@
+//! Ingestion of the raw data on a background worker thread, decoupled from the threads querying the statistics.
+
+use crate::{
+    convertfloat::LossyFloatConvertible, ConverterFromRaw, Error, Report, RollingStats,
+    WindowStorage,
+};
+use rand_distr::{num_traits::Float, Distribution, StandardNormal};
+use std::{
+    io::Write,
+    ops::Add,
+    sync::{mpsc, Arc, Mutex, PoisonError},
+    thread::JoinHandle,
+};
+
+/// A cheap handle to the latest report of a window ingesting the raw data on a worker thread.
+#[derive(Clone)]
+pub struct StatsHandle<O = f32> {
+    snapshot: Arc<Mutex<Report<O>>>,
+}
+
+impl<O: Copy> StatsHandle<O> {
+    /// Returns the report of the window after the last received raw data.
+    pub fn latest(&self) -> Report<O> {
+        *self.snapshot.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// A window ingesting the raw data on a worker thread, returned by the `spawn` method.
+/// # Type parameters
+/// * `S` - the type of the window
+/// * `O` - the float type the statistics are computed in
+pub struct Worker<S, O = f32> {
+    /// The sender of the raw data to be written into the window, can be cloned for multiple producers.
+    pub sender: mpsc::Sender<Vec<u8>>,
+    /// The handle to the latest report of the window.
+    pub stats: StatsHandle<O>,
+    /// The worker thread, returning the window once all of the senders are dropped.
+    pub thread: JoinHandle<Result<S, Error>>,
+}
+
+impl<S, O> Worker<S, O> {
+    /// Drops the sender and waits for the worker thread to write the raw data sent so far, returning the window.
+    /// Other clones of the sender have to be dropped as well for the worker thread to end.
+    /// # Panics
+    /// Resumes the panic of the worker thread if it panicked.
+    pub fn finish(self) -> Result<S, Error> {
+        drop(self.sender);
+        self.thread
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    }
+}
+
+impl<T, E, const WINDOW_SIZE: usize, O, S> RollingStats<T, E, WINDOW_SIZE, O, S>
+where
+    T: Copy + Default + PartialOrd + Add<T, Output = T> + LossyFloatConvertible<O>,
+    E: ConverterFromRaw<T>,
+    O: Float + Send + 'static,
+    StandardNormal: Distribution<O>,
+    Self: Send + 'static,
+    S: WindowStorage<T>,
+{
+    /// Moves the window into a new worker thread, writing the raw data sent using the sender of the returned `Worker` into the window
+    /// and publishing the report of the window after each received raw data to its stats handle.
+    ///
+    /// The worker thread ends once all of the senders are dropped, returning the window,
+    /// or once writing the raw data fails, returning the error.
+    pub fn spawn(mut self) -> Worker<Self, O> {
+        let (sender, receiver) = mpsc::channel::<Vec<u8>>();
+        let handle = StatsHandle {
+            snapshot: Arc::new(Mutex::new(self.report())),
+        };
+
+        let snapshot = handle.snapshot.clone();
+        let thread = std::thread::spawn(move || {
+            for raw in receiver {
+                self.write_all(&raw)?;
+                *snapshot.lock().unwrap_or_else(PoisonError::into_inner) = self.report();
+            }
+
+            Ok(self)
+        });
+
+        Worker {
+            sender,
+            stats: handle,
+            thread,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BigEndian, RollingStats};
+
+    #[test]
+    fn ingests_on_worker() {
+        let worker = RollingStats::<i32, BigEndian, 2>::new().spawn();
+        let handle = worker.stats.clone();
+        assert_eq!(handle.latest().len, 0);
+
+        worker.sender.send(vec![0, 0, 0, 1, 0, 0]).unwrap();
+        worker.sender.send(vec![0, 3]).unwrap();
+        let roller = worker.finish().unwrap();
+
+        assert_eq!(roller.buffer, vec![1, 3]);
+        assert_eq!(handle.latest().mean, 2.0);
+        assert_eq!(handle.latest().max, 3.0);
+    }
+}